@@ -0,0 +1,55 @@
+//! Compare the ways of parsing an API response into a [`Campaign`]
+//!
+//! Run with `cargo bench --bench parse`. Each entry point parses the saved example response in a
+//! loop, and the average time per parse is printed.
+
+use relay_st_jude::Campaign;
+use serde_json::Value;
+
+use std::convert::TryFrom;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RESPONSE: &str = include_str!("../src/example-response.json");
+
+/// Long enough to smooth out noise, short enough not to be annoying
+const BUDGET: Duration = Duration::from_millis(500);
+
+fn bench(name: &str, mut parse: impl FnMut() -> Campaign) {
+    // Warm up, and make sure it works at all
+    black_box(parse());
+
+    let start = Instant::now();
+    let mut iterations = 0u32;
+    while start.elapsed() < BUDGET {
+        black_box(parse());
+        iterations += 1;
+    }
+
+    println!(
+        "{:24} {:>10.2?} per parse ({} iterations)",
+        name,
+        start.elapsed() / iterations,
+        iterations
+    );
+}
+
+fn main() {
+    let bytes = RESPONSE.as_bytes();
+    let value: Value = serde_json::from_str(RESPONSE).unwrap();
+
+    // What fetch_json callers pay: the body is copied into a checked String before parsing
+    bench("String, then from_slice", || {
+        let owned = String::from_utf8(black_box(bytes).to_vec()).unwrap();
+        Campaign::from_slice(owned.as_bytes()).unwrap()
+    });
+    bench("from_slice", || {
+        Campaign::from_slice(black_box(bytes)).unwrap()
+    });
+    bench("try_from(Value)", || {
+        Campaign::try_from(black_box(value.clone())).unwrap()
+    });
+    bench("try_from(&Value)", || {
+        Campaign::try_from(black_box(&value)).unwrap()
+    });
+}
@@ -0,0 +1,63 @@
+//! Compare reusing cached request bodies against building them fresh every time
+//!
+//! Run with `cargo bench --bench query`. Each case builds the body for a campaign query in a
+//! loop, and the average time per body is printed. Polling one campaign is the common case; the
+//! rotation through more campaigns than the cache holds is the worst case for the cache.
+
+use relay_st_jude::bench::{cached_body, fresh_body};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const VANITY: &str = "@relay-fm";
+const SLUG: &str = "relay-st-jude-21";
+
+/// One more than the cache holds, so every lookup misses
+const ROTATION: [&str; 9] = [
+    "campaign-1",
+    "campaign-2",
+    "campaign-3",
+    "campaign-4",
+    "campaign-5",
+    "campaign-6",
+    "campaign-7",
+    "campaign-8",
+    "campaign-9",
+];
+
+/// Long enough to smooth out noise, short enough not to be annoying
+const BUDGET: Duration = Duration::from_millis(500);
+
+fn bench(name: &str, mut build: impl FnMut(u32) -> usize) {
+    // Warm up, and fill the cache
+    black_box(build(0));
+
+    let start = Instant::now();
+    let mut iterations = 0u32;
+    while start.elapsed() < BUDGET {
+        black_box(build(iterations));
+        iterations += 1;
+    }
+
+    println!(
+        "{:24} {:>10.2?} per body ({} iterations)",
+        name,
+        start.elapsed() / iterations,
+        iterations
+    );
+}
+
+fn main() {
+    let rotating = |i: u32| ROTATION[i as usize % ROTATION.len()];
+
+    bench("fresh", |_| fresh_body(black_box(VANITY), black_box(SLUG)));
+    bench("cached", |_| {
+        cached_body(black_box(VANITY), black_box(SLUG))
+    });
+    bench("fresh, rotating", |i| {
+        fresh_body(black_box(VANITY), black_box(rotating(i)))
+    });
+    bench("cached, rotating", |i| {
+        cached_body(black_box(VANITY), black_box(rotating(i)))
+    });
+}
@@ -0,0 +1,115 @@
+#ifndef RELAY_ST_JUDE_H
+#define RELAY_ST_JUDE_H
+
+/* C header for src/ffi.rs. Regenerate with `cbindgen --config cbindgen.toml`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status codes returned by the fallible functions in this module
+typedef enum RsjStatus {
+  // Everything went fine
+  RsjStatus_Ok = 0,
+  // A required pointer argument was null
+  RsjStatus_NullPointer = 1,
+  // A string argument was not valid UTF-8
+  RsjStatus_InvalidUtf8 = 2,
+  // Fetching or parsing the campaign failed
+  RsjStatus_Fetch = 3,
+  // A milestone index was past the end of the list
+  RsjStatus_OutOfBounds = 4,
+  // The pointer was not a live value from this library, e.g. it was already freed
+  RsjStatus_InvalidHandle = 5,
+  // The library panicked. This is a bug!
+  RsjStatus_Panic = 6,
+} RsjStatus;
+
+// An opaque handle to a fetched [`Campaign`]
+typedef struct RsjCampaign RsjCampaign;
+
+// Fetch a campaign by vanity & slug from online
+//
+// Returns null on failure; call [`rsj_last_error_message`] for details.
+// Free the result with [`rsj_campaign_free`].
+//
+// # Safety
+// `vanity` and `slug` must each be null or point to a nul-terminated string.
+struct RsjCampaign *rsj_fetch(const char *vanity, const char *slug);
+
+// Parse a campaign from a saved API response, without touching the network
+//
+// Returns null on failure; call [`rsj_last_error_message`] for details.
+// Free the result with [`rsj_campaign_free`].
+//
+// # Safety
+// `json` must be null or point to a nul-terminated string.
+struct RsjCampaign *rsj_campaign_from_json(const char *json);
+
+// Free a campaign returned by [`rsj_fetch`] or [`rsj_campaign_from_json`]
+//
+// Passing null is a no-op. Passing an already-freed campaign returns
+// [`RsjStatus::InvalidHandle`] and leaves memory alone.
+//
+// # Safety
+// `campaign` must be null or a pointer previously returned by this library.
+enum RsjStatus rsj_campaign_free(struct RsjCampaign *campaign);
+
+// The campaign's name as a UTF-8 string
+//
+// Returns null on failure. Free the result with [`rsj_string_free`].
+//
+// # Safety
+// `campaign` must be null or a pointer previously returned by this library.
+char *rsj_campaign_name(const struct RsjCampaign *campaign);
+
+// The amount raised so far, in USD
+//
+// # Safety
+// `campaign` must be null or a pointer previously returned by this library, and `out` must be
+// null or valid for writes.
+enum RsjStatus rsj_campaign_raised(const struct RsjCampaign *campaign, double *out);
+
+// The campaign's goal, in USD
+//
+// # Safety
+// `campaign` must be null or a pointer previously returned by this library, and `out` must be
+// null or valid for writes.
+enum RsjStatus rsj_campaign_goal(const struct RsjCampaign *campaign, double *out);
+
+// How many milestones the campaign has
+//
+// # Safety
+// `campaign` must be null or a pointer previously returned by this library, and `out` must be
+// null or valid for writes.
+enum RsjStatus rsj_campaign_milestone_count(const struct RsjCampaign *campaign, uintptr_t *out);
+
+// The description and amount of the milestone at `index`
+//
+// Milestones are in the order the API returned them. On success, `*out_description` must be
+// freed with [`rsj_string_free`]. `*out_amount` is NaN for a milestone without an amount yet.
+//
+// # Safety
+// `campaign` must be null or a pointer previously returned by this library, and the out pointers
+// must be null or valid for writes.
+enum RsjStatus rsj_campaign_milestone_at(const struct RsjCampaign *campaign,
+                                         uintptr_t index,
+                                         char **out_description,
+                                         double *out_amount);
+
+// A description of the most recent error on this thread
+//
+// Returns null if nothing has failed yet. Free the result with [`rsj_string_free`].
+char *rsj_last_error_message(void);
+
+// Free a string returned by this library
+//
+// Passing null is a no-op. Passing an already-freed string returns
+// [`RsjStatus::InvalidHandle`] and leaves memory alone.
+//
+// # Safety
+// `s` must be null or a pointer previously returned by this library.
+enum RsjStatus rsj_string_free(char *s);
+
+#endif /* RELAY_ST_JUDE_H */
@@ -0,0 +1,170 @@
+use crate::Report;
+
+use serde::{Deserialize, Serialize};
+
+use std::io::Read;
+
+/// Relative image paths are relative to this
+const SITE_URL: &str = "https://tiltify.com";
+
+/// A picture for a [`Campaign`](crate::Campaign) or [`Team`](crate::Team)
+///
+/// Everything but `src` is optional, so hand-written or older responses that only have the URL
+/// still parse.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Avatar {
+    /// Where to download the image from, as the API sent it
+    ///
+    /// Use [`Avatar::url`] for a URL that's always absolute.
+    pub src: String,
+
+    /// Text describing the image, for screen readers
+    #[serde(default)]
+    pub alt: String,
+
+    /// The image's width in pixels, if known
+    #[serde(default)]
+    pub width: Option<u32>,
+
+    /// The image's height in pixels, if known
+    #[serde(default)]
+    pub height: Option<u32>,
+}
+
+impl Avatar {
+    /// An avatar with only a `src`
+    pub fn new(src: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            alt: String::new(),
+            width: None,
+            height: None,
+        }
+    }
+
+    /// [`src`](Avatar::src) as an absolute URL
+    ///
+    /// Paths like `/uploads/a.png` are resolved against `https://tiltify.com`, and
+    /// protocol-relative URLs like `//assets.tiltify.com/a.png` get `https:`.
+    pub fn url(&self) -> String {
+        let src = self.src.trim();
+        if src.contains("://") {
+            src.to_string()
+        } else if let Some(rest) = src.strip_prefix("//") {
+            format!("https://{}", rest)
+        } else {
+            format!("{}/{}", SITE_URL, src.trim_start_matches('/'))
+        }
+    }
+
+    /// Download the image's bytes
+    ///
+    /// Any response other than a success is an error, with the status code and URL in it.
+    pub fn download(&self) -> Result<Vec<u8>, Report> {
+        let url = self.url();
+        tracing::debug!(url = %url, "downloading avatar");
+
+        let response = ureq::get(&url).call().map_err(|e| match e {
+            ureq::Error::Status(code, response) => Report::msg(format!(
+                "Couldn't download avatar from {}: HTTP {} {}",
+                url,
+                code,
+                response.status_text()
+            )),
+            e => Report::msg(format!("Couldn't download avatar from {}: {}", url, e)),
+        })?;
+
+        let mut bytes = vec![];
+        response.into_reader().read_to_end(&mut bytes)?;
+        tracing::debug!(url = %url, bytes = bytes.len(), "downloaded avatar");
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+    use crate::Campaign;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Answer exactly one request with `status` and `body`, returning the URL to ask
+    fn serve_once(status: &'static str, body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/avatar.png", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n",
+                status,
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(body).unwrap();
+        });
+
+        url
+    }
+
+    #[test]
+    fn parses() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(
+            campaign.avatar,
+            Some(Avatar {
+                src: "https://assets.tiltify.com/uploads/event/thumbnail/121745/blob-4eeff29f-7090-4aba-bb8c-098bb7887576.png".to_string(),
+                alt: "alt".to_string(),
+                width: Some(64),
+                height: Some(64),
+            })
+        );
+
+        // Older responses didn't have one
+        let campaign =
+            Campaign::from_json(include_str!("example-response-null-amount.json")).unwrap();
+        assert_eq!(campaign.avatar, None);
+    }
+
+    #[test]
+    fn urls() {
+        let url = |src| Avatar::new(src).url();
+
+        assert_eq!(
+            url("https://assets.tiltify.com/a.png"),
+            "https://assets.tiltify.com/a.png"
+        );
+        assert_eq!(
+            url("http://localhost:8000/a.png"),
+            "http://localhost:8000/a.png"
+        );
+        assert_eq!(
+            url("//assets.tiltify.com/a.png"),
+            "https://assets.tiltify.com/a.png"
+        );
+        assert_eq!(url("/uploads/a.png"), "https://tiltify.com/uploads/a.png");
+        assert_eq!(url("uploads/a.png"), "https://tiltify.com/uploads/a.png");
+    }
+
+    #[test]
+    fn downloads() {
+        let avatar = Avatar::new(serve_once("200 OK", b"\x89PNG"));
+        assert_eq!(avatar.download().unwrap(), b"\x89PNG");
+
+        let avatar = Avatar::new(serve_once("404 Not Found", b"nope"));
+        let err = avatar.download().unwrap_err().to_string();
+        assert!(err.contains("HTTP 404 Not Found"), "{}", err);
+        assert!(err.contains(&avatar.src), "{}", err);
+    }
+}
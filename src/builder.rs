@@ -0,0 +1,204 @@
+use crate::{Avatar, Campaign, CampaignId, CampaignStatus, Milestone, Team, Usd};
+
+use std::time::SystemTime;
+
+/// Build a [`Campaign`] in code, like for tests, without writing out a whole API response
+///
+/// Anything not set is empty: no id, name, or description, $0 raised towards a $0 goal, and no
+/// milestones. Amounts are in dollars and rounded to the nearest cent, the same as [`Usd::new`].
+///
+/// ```rust
+/// use relay_st_jude::{Campaign, Usd};
+///
+/// let campaign = Campaign::builder()
+///     .name("Relay FM for St. Jude 2021")
+///     .goal(333_333.33)
+///     .raised(22_663.40)
+///     .milestone("Myke and Stephen attempt Flight Simulator again", 20_000.)
+///     .build();
+///
+/// assert_eq!(campaign.total_amount_raised, Usd::new(22_663.40));
+/// assert_eq!(campaign.percent_raised().to_string(), "6.8%");
+/// ```
+#[derive(Clone, Debug)]
+pub struct CampaignBuilder {
+    campaign: Campaign,
+}
+
+impl Campaign {
+    /// Start building a campaign, see [`CampaignBuilder`]
+    pub fn builder() -> CampaignBuilder {
+        CampaignBuilder {
+            campaign: Campaign {
+                id: None,
+                name: String::new(),
+                slug: None,
+                description: String::new(),
+                status: None,
+                avatar: None,
+                team: None,
+                livestream: None,
+                cause: None,
+                total_amount_raised: Usd::ZERO,
+                goal: Usd::ZERO,
+                original_goal: None,
+                published_at: None,
+                ends_at: None,
+                milestones: vec![],
+                rewards: vec![],
+                page: None,
+            },
+        }
+    }
+}
+
+impl CampaignBuilder {
+    /// Set the [`id`](Campaign::id)
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.campaign.id = Some(CampaignId::new(id));
+        self
+    }
+
+    /// Set the [`name`](Campaign::name)
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.campaign.name = name.into();
+        self
+    }
+
+    /// Set the [`slug`](Campaign::slug)
+    pub fn slug(mut self, slug: impl Into<String>) -> Self {
+        self.campaign.slug = Some(slug.into());
+        self
+    }
+
+    /// Set the [`description`](Campaign::description)
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.campaign.description = description.into();
+        self
+    }
+
+    /// Set the [`status`](Campaign::status)
+    pub fn status(mut self, status: CampaignStatus) -> Self {
+        self.campaign.status = Some(status);
+        self
+    }
+
+    /// Set the [`avatar`](Campaign::avatar)
+    pub fn avatar(mut self, avatar: Avatar) -> Self {
+        self.campaign.avatar = Some(avatar);
+        self
+    }
+
+    /// Set the [`team`](Campaign::team), by its name
+    pub fn team(mut self, name: impl Into<String>) -> Self {
+        self.campaign.team = Some(Team {
+            name: name.into(),
+            avatar: None,
+        });
+        self
+    }
+
+    /// Set the [`total_amount_raised`](Campaign::total_amount_raised), in dollars
+    pub fn raised(mut self, usd: f64) -> Self {
+        self.campaign.total_amount_raised = Usd::new(usd);
+        self
+    }
+
+    /// Set the [`goal`](Campaign::goal), in dollars
+    pub fn goal(mut self, usd: f64) -> Self {
+        self.campaign.goal = Usd::new(usd);
+        self
+    }
+
+    /// Set the [`original_goal`](Campaign::original_goal), in dollars
+    pub fn original_goal(mut self, usd: f64) -> Self {
+        self.campaign.original_goal = Some(Usd::new(usd));
+        self
+    }
+
+    /// Set when the campaign was [`published_at`](Campaign::published_at)
+    pub fn published_at(mut self, time: SystemTime) -> Self {
+        self.campaign.published_at = Some(time);
+        self
+    }
+
+    /// Set when the campaign [`ends_at`](Campaign::ends_at)
+    pub fn ends_at(mut self, time: SystemTime) -> Self {
+        self.campaign.ends_at = Some(time);
+        self
+    }
+
+    /// Add a milestone for `usd` dollars, after any added already
+    pub fn milestone(mut self, description: impl Into<String>, usd: f64) -> Self {
+        self.campaign.milestones.push(Milestone {
+            id: None,
+            description: description.into(),
+            amount: Some(Usd::new(usd)),
+        });
+        self
+    }
+
+    /// Add a milestone that doesn't have an amount yet
+    pub fn unpriced_milestone(mut self, description: impl Into<String>) -> Self {
+        self.campaign.milestones.push(Milestone {
+            id: None,
+            description: description.into(),
+            amount: None,
+        });
+        self
+    }
+
+    /// The finished campaign
+    pub fn build(self) -> Campaign {
+        self.campaign
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    /// Building the example response's campaign gives what parsing it does
+    #[test]
+    fn matches_the_example() {
+        let mut parsed = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        // The builder doesn't make up milestone ids
+        for milestone in &mut parsed.milestones {
+            milestone.id = None;
+        }
+        let built = Campaign::builder()
+            .id("121745")
+            .name("Relay FM for St. Jude 2021")
+            .slug("relay-st-jude-21")
+            .description(parsed.description.clone())
+            .status(CampaignStatus::Published)
+            .avatar(Avatar {
+                src: parsed.avatar.as_ref().unwrap().src.clone(),
+                alt: "alt".to_string(),
+                width: Some(64),
+                height: Some(64),
+            })
+            .goal(333_333.33)
+            .original_goal(100.)
+            .raised(22_663.40)
+            .milestone("Stephen & Myke go to space via KSP", 75_000.)
+            .milestone("Stephen dissembles his NeXTCube on stream", 55_000.)
+            .milestone("Myke and Stephen attempt Flight Simulator again", 20_000.)
+            .milestone("$1 million raised in 3 years!", 196_060.44)
+            .build();
+
+        assert_eq!(built, parsed);
+    }
+
+    #[test]
+    fn defaults() {
+        let campaign = Campaign::builder().unpriced_milestone("Secret").build();
+
+        assert_eq!(campaign.name, "");
+        assert_eq!(campaign.goal, Usd::ZERO);
+        assert_eq!(campaign.status, None);
+        assert_eq!(campaign.team, None);
+        assert_eq!(campaign.avatar, None);
+        assert_eq!(campaign.milestones[0].amount, None);
+    }
+}
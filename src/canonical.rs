@@ -0,0 +1,235 @@
+//! A byte-for-byte stable JSON form of a [`Campaign`], for diffing and archiving
+
+use crate::{decimal_string, timestamp, Campaign, CampaignStatus, ListOptions, Usd};
+
+use serde_json::{json, Map, Value};
+
+impl Campaign {
+    /// Serialize to JSON that only changes when the campaign does
+    ///
+    /// The output is stable across releases, for diffing or archiving snapshots:
+    ///
+    /// - Object keys are sorted
+    /// - Amounts are strings with exactly two decimals, like `"22663.40"`
+    /// - Milestones are in [`Campaign::milestone_page`]'s default order, not the API's
+    /// - It's pretty-printed with two-space indents, and ends with a newline
+    ///
+    /// The shape matches the API's, so the output parses back into an equal `Campaign`.
+    pub fn to_canonical_json(&self) -> String {
+        let amount = |usd: Option<Usd>| {
+            json!({
+                "currency": "USD",
+                "value": usd.map(|usd| decimal_string(usd.cents())),
+            })
+        };
+
+        let milestones: Vec<Value> = self
+            .milestone_page(&ListOptions::default())
+            .milestones
+            .into_iter()
+            .map(|m| {
+                json!({
+                    "id": m.id.as_ref().map(|id| id.as_str()),
+                    "name": m.description,
+                    "amount": amount(m.amount),
+                })
+            })
+            .collect();
+
+        let rewards: Vec<Value> = self
+            .rewards
+            .iter()
+            .map(|r| {
+                json!({
+                    "name": r.name,
+                    "description": r.description,
+                    "amount": amount(Some(r.amount)),
+                    "quantity": r.quantity,
+                    "remaining": r.remaining,
+                    "active": r.active,
+                })
+            })
+            .collect();
+
+        let campaign = json!({
+            "data": {
+                "campaign": {
+                    "avatar": self.avatar,
+                    "id": self.id.as_ref().map(|id| id.as_str()),
+                    "name": self.name,
+                    "slug": self.slug,
+                    "description": self.description,
+                    "status": self.status.map(status_name),
+                    "team": self.team,
+                    "livestream": self.livestream,
+                    "cause": self.cause,
+                    "totalAmountRaised": amount(Some(self.total_amount_raised)),
+                    "goal": amount(Some(self.goal)),
+                    "originalGoal": self.original_goal.map(|goal| amount(Some(goal))),
+                    "publishedAt": self.published_at.map(timestamp::rfc3339_millis),
+                    "endsAt": self.ends_at.map(timestamp::rfc3339_millis),
+                    "milestones": milestones,
+                    "rewards": rewards,
+                },
+            },
+        });
+
+        let mut json = serde_json::to_string_pretty(&sorted(campaign))
+            .expect("JSON values are always serializable");
+        json.push('\n');
+        json
+    }
+}
+
+fn status_name(status: CampaignStatus) -> &'static str {
+    match status {
+        CampaignStatus::Unpublished => "unpublished",
+        CampaignStatus::Published => "published",
+        CampaignStatus::Retired => "retired",
+        CampaignStatus::Unknown => "unknown",
+    }
+}
+
+/// Rebuild every object with its keys inserted in order
+///
+/// serde_json keeps insertion order when its `preserve_order` feature is on, and some other
+/// crate in a build could turn it on. This makes the order independent of that.
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut object = Map::new();
+            for (key, value) in entries {
+                object.insert(key, sorted(value));
+            }
+            Value::Object(object)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    const RESPONSES: &[&str] = &[
+        include_str!("example-response.json"),
+        include_str!("example-response-unpublished.json"),
+        include_str!("example-response-null-amount.json"),
+        include_str!("example-response-team.json"),
+        include_str!("example-response-livestream.json"),
+        include_str!("example-response-livestream-youtube.json"),
+        include_str!("example-response-schedule.json"),
+        include_str!("example-response-cause.json"),
+        include_str!("example-response-rewards.json"),
+    ];
+
+    #[test]
+    fn is_stable() {
+        for response in RESPONSES {
+            let campaign = Campaign::from_json(response).unwrap();
+            let json = campaign.to_canonical_json();
+
+            assert_eq!(json, campaign.clone().to_canonical_json());
+            assert!(json.ends_with("}\n"), "{}", json);
+
+            // Parsing it back gives the same campaign, which gives the same bytes
+            let parsed = Campaign::from_json(&json).unwrap();
+            assert_eq!(parsed.to_canonical_json(), json);
+            assert_eq!(parsed.name, campaign.name);
+            assert_eq!(parsed.status, campaign.status);
+        }
+    }
+
+    #[test]
+    fn shape() {
+        let campaign = Campaign::from_json(RESPONSES[2]).unwrap();
+        let json = campaign.to_canonical_json();
+
+        assert_eq!(
+            json,
+            indoc::indoc!(
+                r#"
+                {
+                  "data": {
+                    "campaign": {
+                      "avatar": null,
+                      "cause": null,
+                      "description": "A campaign with a milestone saved before its amount was set",
+                      "endsAt": null,
+                      "goal": {
+                        "currency": "USD",
+                        "value": "333333.33"
+                      },
+                      "id": "121745",
+                      "livestream": null,
+                      "milestones": [
+                        {
+                          "amount": {
+                            "currency": "USD",
+                            "value": "20000.00"
+                          },
+                          "id": null,
+                          "name": "Myke and Stephen attempt Flight Simulator again"
+                        },
+                        {
+                          "amount": {
+                            "currency": "USD",
+                            "value": "55000.00"
+                          },
+                          "id": null,
+                          "name": "Stephen dissembles his NeXTCube on stream"
+                        },
+                        {
+                          "amount": {
+                            "currency": "USD",
+                            "value": null
+                          },
+                          "id": null,
+                          "name": "Something secret"
+                        }
+                      ],
+                      "name": "Relay FM for St. Jude 2021",
+                      "originalGoal": null,
+                      "publishedAt": null,
+                      "rewards": [],
+                      "slug": null,
+                      "status": "published",
+                      "team": null,
+                      "totalAmountRaised": {
+                        "currency": "USD",
+                        "value": "22663.40"
+                      }
+                    }
+                  }
+                }
+                "#
+            )
+        );
+    }
+
+    /// Milestones that only differ in API order serialize the same
+    #[test]
+    fn ignores_api_order() {
+        let campaign = Campaign::from_json(RESPONSES[0]).unwrap();
+        let mut reversed = campaign.clone();
+        reversed.milestones.reverse();
+
+        assert_eq!(campaign.to_canonical_json(), reversed.to_canonical_json());
+    }
+
+    #[test]
+    fn odd_amounts() {
+        let mut campaign = Campaign::from_json(RESPONSES[0]).unwrap();
+        campaign.total_amount_raised = Usd::new(-0.5);
+        campaign.goal = Usd::new(0.005);
+
+        let json: Value = serde_json::from_str(&campaign.to_canonical_json()).unwrap();
+        let campaign = &json["data"]["campaign"];
+        assert_eq!(campaign["totalAmountRaised"]["value"], "-0.50");
+        assert_eq!(campaign["goal"]["value"], "0.00");
+    }
+}
@@ -0,0 +1,298 @@
+//! Pluggable rules for when a milestone counts as completed
+//!
+//! By default a milestone is completed once the campaign's total reaches its amount. Some
+//! communities define it differently for a few milestones, like "once the whole event passes
+//! $X" for a milestone shared across co-fundraisers. Those milestones can be given their own
+//! [`CompletionRule`] in a [`CompletionRules`] map. Everything else keeps using
+//! [`AmountReached`].
+//!
+//! Rules can be keyed by the milestone's [`MilestoneId`], which survives organizers editing the
+//! milestone, or by its description, for milestones parsed from responses without ids. A rule for
+//! the id wins over one for the description, the same way [`Campaign::diff`] matches milestones.
+//!
+//! ```rust
+//! use relay_st_jude::completion::{CompletionRules, Context, EventTotalReached, Manual};
+//! use relay_st_jude::{Campaign, Usd};
+//!
+//! # fn check(campaign: &Campaign) {
+//! let mut rules = CompletionRules::new();
+//! rules.insert("Everyone shaves their heads", EventTotalReached(Usd::new(1_000_000.)));
+//! rules.insert("Mystery stream", Manual(true));
+//!
+//! let context = Context {
+//!     campaign,
+//!     event_total: Some(Usd::new(1_250_000.)),
+//! };
+//! for milestone in &campaign.milestones {
+//!     println!("{}: {}", milestone.description, rules.is_completed(milestone, &context));
+//! }
+//! # }
+//! ```
+
+use crate::{Campaign, Milestone, MilestoneId, Usd};
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What a [`CompletionRule`] gets to look at
+#[derive(Copy, Clone, Debug)]
+pub struct Context<'a> {
+    /// The campaign the milestone belongs to
+    pub campaign: &'a Campaign,
+
+    /// The total across the whole fundraising event, if you know it
+    ///
+    /// Tiltify's campaign query doesn't include this, so it's up to you to supply it.
+    pub event_total: Option<Usd>,
+}
+
+impl<'a> Context<'a> {
+    /// Just the campaign, with no event total
+    pub fn new(campaign: &'a Campaign) -> Self {
+        Self {
+            campaign,
+            event_total: None,
+        }
+    }
+}
+
+/// Decides whether one milestone is completed
+pub trait CompletionRule: fmt::Debug + Send + Sync {
+    /// Whether `milestone` is completed, given `context`
+    fn is_completed(&self, milestone: &Milestone, context: &Context) -> bool;
+}
+
+/// Completed once the campaign total reaches the milestone's amount
+///
+/// This is the rule for every milestone without one of its own. Milestones without an amount are
+/// never completed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct AmountReached;
+
+impl CompletionRule for AmountReached {
+    fn is_completed(&self, milestone: &Milestone, context: &Context) -> bool {
+        milestone.is_completed(context.campaign.total_amount_raised)
+    }
+}
+
+/// Completed once the whole event's total reaches this amount
+///
+/// Never completed while [`Context::event_total`] is unknown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EventTotalReached(pub Usd);
+
+impl CompletionRule for EventTotalReached {
+    fn is_completed(&self, _: &Milestone, context: &Context) -> bool {
+        context.event_total.is_some_and(|total| total >= self.0)
+    }
+}
+
+/// Completed when you say so
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Manual(pub bool);
+
+impl CompletionRule for Manual {
+    fn is_completed(&self, _: &Milestone, _: &Context) -> bool {
+        self.0
+    }
+}
+
+/// Per-milestone [`CompletionRule`]s, falling back to [`AmountReached`]
+#[derive(Debug, Default)]
+pub struct CompletionRules {
+    by_id: BTreeMap<MilestoneId, Box<dyn CompletionRule>>,
+    by_description: BTreeMap<String, Box<dyn CompletionRule>>,
+}
+
+impl CompletionRules {
+    /// No special rules: every milestone uses [`AmountReached`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `rule` for the milestone described as `milestone`, replacing any rule it had
+    ///
+    /// Prefer [`insert_for_id`](CompletionRules::insert_for_id) when the milestone has an id,
+    /// since a rule for a description stops applying once the description is edited.
+    pub fn insert(&mut self, milestone: impl Into<String>, rule: impl CompletionRule + 'static) {
+        self.by_description.insert(milestone.into(), Box::new(rule));
+    }
+
+    /// Use `rule` for the milestone with id `milestone`, replacing any rule it had
+    pub fn insert_for_id(&mut self, milestone: MilestoneId, rule: impl CompletionRule + 'static) {
+        self.by_id.insert(milestone, Box::new(rule));
+    }
+
+    /// The rule that applies to `milestone`: the one for its id, then the one for its
+    /// description, then [`AmountReached`]
+    pub fn rule_for(&self, milestone: &Milestone) -> &dyn CompletionRule {
+        let by_id = milestone.id.as_ref().and_then(|id| self.by_id.get(id));
+        match by_id.or_else(|| self.by_description.get(&milestone.description)) {
+            Some(rule) => rule.as_ref(),
+            None => &AmountReached,
+        }
+    }
+
+    /// Whether `milestone` is completed, using its own rule if it has one
+    pub fn is_completed(&self, milestone: &Milestone, context: &Context) -> bool {
+        self.rule_for(milestone).is_completed(milestone, context)
+    }
+
+    /// Milestones that are completed in `after`, but weren't in `before`
+    ///
+    /// Each of `after`'s milestones is checked against both contexts, so a milestone added
+    /// between polls only counts if the update itself completed it. [`Manual`] milestones read
+    /// the same both times, so they never show up here.
+    pub fn newly_completed<'a>(&self, before: &Context, after: &Context<'a>) -> Vec<&'a Milestone> {
+        after
+            .campaign
+            .milestones
+            .iter()
+            .filter(|m| self.is_completed(m, after) && !self.is_completed(m, before))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn milestone(description: &str, amount: Option<f64>) -> Milestone {
+        Milestone {
+            id: None,
+            description: description.to_string(),
+            amount: amount.map(Usd::new),
+        }
+    }
+
+    fn campaign(total: f64) -> Campaign {
+        Campaign::builder()
+            .name("Test")
+            .raised(total)
+            .goal(1_000.)
+            .milestone("small", 100.)
+            .milestone("big", 500.)
+            .milestone("team", 50.)
+            .unpriced_milestone("secret")
+            .build()
+    }
+
+    fn completed(rules: &CompletionRules, context: &Context) -> Vec<String> {
+        context
+            .campaign
+            .milestones
+            .iter()
+            .filter(|m| rules.is_completed(m, context))
+            .map(|m| m.description.clone())
+            .collect()
+    }
+
+    #[test]
+    fn defaults_to_amount_reached() {
+        let campaign = campaign(100.);
+        let context = Context::new(&campaign);
+
+        assert_eq!(
+            completed(&CompletionRules::new(), &context),
+            ["small", "team"]
+        );
+    }
+
+    /// A milestone's own rule wins over its amount, in both directions
+    #[test]
+    fn own_rule_takes_precedence() {
+        let mut rules = CompletionRules::new();
+        rules.insert("team", EventTotalReached(Usd::new(10_000.)));
+        rules.insert("secret", Manual(true));
+        rules.insert("small", Manual(false));
+        // Inserting again replaces
+        rules.insert("small", Manual(true));
+
+        let campaign = campaign(100.);
+        let mut context = Context::new(&campaign);
+
+        // The event total isn't known, so the team milestone isn't done even though its amount is
+        assert_eq!(completed(&rules, &context), ["small", "secret"]);
+
+        context.event_total = Some(Usd::new(9_999.99));
+        assert_eq!(completed(&rules, &context), ["small", "secret"]);
+
+        context.event_total = Some(Usd::new(10_000.));
+        assert_eq!(completed(&rules, &context), ["small", "team", "secret"]);
+    }
+
+    /// A rule for an id keeps applying when the description is edited, and wins over one for the
+    /// description
+    #[test]
+    fn rules_by_id() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let ksp = campaign.milestones[0].clone();
+        assert_eq!(ksp.description, "Stephen & Myke go to space via KSP");
+
+        let mut rules = CompletionRules::new();
+        rules.insert_for_id(ksp.id.clone().unwrap(), Manual(true));
+        rules.insert(ksp.description.clone(), Manual(false));
+        let context = Context::new(&campaign);
+        assert!(rules.is_completed(&campaign.milestones[0], &context));
+
+        campaign.milestones[0].description = "Stephen & Myke go to orbit via KSP".to_string();
+        let context = Context::new(&campaign);
+        assert!(rules.is_completed(&campaign.milestones[0], &context));
+
+        // Without an id, only the description can match
+        let unnamed = Milestone { id: None, ..ksp };
+        assert!(!rules.is_completed(&unnamed, &context));
+        rules.insert(unnamed.description.clone(), Manual(true));
+        assert!(rules.is_completed(&unnamed, &context));
+    }
+
+    #[test]
+    fn newly_completed_by_amount() {
+        let rules = CompletionRules::new();
+        let (before, after) = (campaign(99.99), campaign(500.));
+
+        let newly = rules.newly_completed(&Context::new(&before), &Context::new(&after));
+        let names: Vec<_> = newly.iter().map(|m| m.description.as_str()).collect();
+        assert_eq!(names, ["small", "big"]);
+
+        // Nothing changed, nothing new
+        assert!(rules
+            .newly_completed(&Context::new(&after), &Context::new(&after))
+            .is_empty());
+    }
+
+    #[test]
+    fn newly_completed_by_event_total() {
+        let mut rules = CompletionRules::new();
+        rules.insert("team", EventTotalReached(Usd::new(10_000.)));
+        rules.insert("secret", Manual(true));
+
+        // The campaign didn't move, but the event did
+        let campaign = campaign(10.);
+        let before = Context {
+            campaign: &campaign,
+            event_total: Some(Usd::new(9_000.)),
+        };
+        let after = Context {
+            event_total: Some(Usd::new(12_000.)),
+            ..before
+        };
+
+        let newly = rules.newly_completed(&before, &after);
+        let names: Vec<_> = newly.iter().map(|m| m.description.as_str()).collect();
+        assert_eq!(names, ["team"]);
+    }
+
+    /// A milestone added between polls below the old total wasn't completed by this update
+    #[test]
+    fn added_milestones_need_crossing() {
+        let rules = CompletionRules::new();
+        let before = campaign(200.);
+        let mut after = campaign(200.);
+        after.milestones.push(milestone("late", Some(150.)));
+
+        assert!(rules
+            .newly_completed(&Context::new(&before), &Context::new(&after))
+            .is_empty());
+    }
+}
@@ -0,0 +1,448 @@
+//! Amounts in whatever currency Tiltify sent them in
+//!
+//! [`Campaign`](crate::Campaign) assumes every amount is in US dollars, which is true for St. Jude
+//! campaigns. For anything else, [`RawCampaign`] keeps each amount's currency alongside it, and
+//! [`CurrencyAmount::try_into_usd`] converts only when that's safe.
+//!
+//! Going the other way, [`Usd::convert`] shows a US dollar amount in another currency, at rates
+//! from any [`ExchangeRates`] you plug in. The crate doesn't ship rates of its own.
+//!
+//! ```rust
+//! use relay_st_jude::{Currency, StaticRates, Usd};
+//!
+//! let mut rates = StaticRates::new();
+//! rates.insert(Currency::Gbp, 0.79);
+//!
+//! let raised = Usd::new(22_663.40).convert(&rates, Currency::Gbp).unwrap();
+//! assert_eq!(raised.to_string(), "17904.09 GBP");
+//! ```
+
+use crate::{decimal_string, ApiResponse, Report, RoundingPolicy, StrCents, Usd};
+
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// The currency an amount is in
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Currency {
+    /// United States dollars
+    Usd,
+
+    /// Canadian dollars
+    Cad,
+
+    /// Pounds sterling
+    Gbp,
+
+    /// Euros
+    Eur,
+
+    /// Any other currency, by its code as Tiltify sent it
+    Other(String),
+}
+
+impl Currency {
+    /// The ISO 4217 code, like `USD`
+    pub fn code(&self) -> &str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Cad => "CAD",
+            Currency::Gbp => "GBP",
+            Currency::Eur => "EUR",
+            Currency::Other(code) => code,
+        }
+    }
+
+    fn from_code(code: String) -> Self {
+        match code.as_str() {
+            "USD" => Currency::Usd,
+            "CAD" => Currency::Cad,
+            "GBP" => Currency::Gbp,
+            "EUR" => Currency::Eur,
+            _ => Currency::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Currency::from_code)
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+/// An amount of money in any currency, exactly as the API sent it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CurrencyAmount {
+    /// What currency `value` is in
+    pub currency: Currency,
+
+    /// The amount as a decimal string, like `"22663.40"`
+    ///
+    /// Amounts sent as JSON numbers are kept as their decimal text.
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub value: String,
+}
+
+impl CurrencyAmount {
+    /// Convert into [`Usd`], failing if this amount is in another currency
+    pub fn try_into_usd(&self) -> Result<Usd, Report> {
+        if self.currency != Currency::Usd {
+            return Err(Report::msg(format!(
+                "Amount {} is in {}, not USD",
+                self.value, self.currency
+            )));
+        }
+
+        let value: StrDeserializer<ValueError> = self.value.as_str().into_deserializer();
+        StrCents::deserialize(value)
+            .map(|cents| Usd::from_cents(cents.0))
+            .map_err(|e| Report::msg(format!("Invalid amount {:?}: {}", self.value, e)))
+    }
+}
+
+impl fmt::Display for CurrencyAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.currency)
+    }
+}
+
+/// A source of exchange rates from US dollars, for [`Usd::convert`]
+pub trait ExchangeRates {
+    /// How many of `currency` one US dollar buys, if known
+    fn usd_to(&self, currency: &Currency) -> Option<f64>;
+}
+
+/// Fixed [`ExchangeRates`], set ahead of time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StaticRates {
+    rates: HashMap<Currency, f64>,
+}
+
+impl StaticRates {
+    /// No rates at all, so only converting to USD works
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One US dollar buys `rate` of `currency`, replacing any rate it had
+    pub fn insert(&mut self, currency: Currency, rate: f64) {
+        self.rates.insert(currency, rate);
+    }
+}
+
+impl ExchangeRates for StaticRates {
+    fn usd_to(&self, currency: &Currency) -> Option<f64> {
+        self.rates.get(currency).copied()
+    }
+}
+
+impl Usd {
+    /// This amount in `currency`, at `rates`, rounded to the nearest cent
+    ///
+    /// `None` if `rates` doesn't know `currency`, or gives a rate that's negative or not finite.
+    /// Converting to USD always works, and gives the same amount back.
+    pub fn convert(
+        &self,
+        rates: &impl ExchangeRates,
+        currency: Currency,
+    ) -> Option<CurrencyAmount> {
+        let cents = if currency == Currency::Usd {
+            self.cents()
+        } else {
+            let rate = rates.usd_to(&currency)?;
+            let converted = self.usd() * rate;
+            if !(rate >= 0. && converted.is_finite()) {
+                return None;
+            }
+            RoundingPolicy::default().round_cents(converted)
+        };
+
+        Some(CurrencyAmount {
+            currency,
+            value: decimal_string(cents),
+        })
+    }
+}
+
+fn deserialize_decimal<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawValue {
+        Str(String),
+        Num(serde_json::Number),
+    }
+
+    Ok(match RawValue::deserialize(deserializer)? {
+        RawValue::Str(s) => s,
+        RawValue::Num(n) => n.to_string(),
+    })
+}
+
+/// Why a [`Campaign`](crate::Campaign) couldn't be parsed: its amounts aren't in US dollars
+///
+/// Fetching and parsing campaigns return this inside their [`Report`], so check for it with
+/// `report.downcast_ref::<UnsupportedCurrency>()`. [`RawCampaign`] can still read the campaign.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedCurrency {
+    /// The first currency that wasn't USD
+    pub found: Currency,
+}
+
+impl UnsupportedCurrency {
+    /// Amounts without a currency are assumed to be USD
+    pub(crate) fn check(currency: Option<Currency>) -> Result<(), Self> {
+        match currency {
+            None | Some(Currency::Usd) => Ok(()),
+            Some(found) => Err(Self { found }),
+        }
+    }
+
+    /// The first amount in `response` that isn't in USD
+    pub(crate) fn in_response(response: ApiResponse<RawCampaign>) -> Option<Self> {
+        let campaign = response.into_campaign().ok()?;
+        let mut amounts = vec![campaign.total_amount_raised, campaign.goal];
+        amounts.extend(campaign.milestones.into_iter().filter_map(|m| m.amount));
+
+        amounts
+            .into_iter()
+            .find_map(|amount| Self::check(Some(amount.currency)).err())
+    }
+}
+
+impl fmt::Display for UnsupportedCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Campaign amounts are in {}, not USD. Use RawCampaign to read them",
+            self.found
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedCurrency {}
+
+/// A [`Campaign`](crate::Campaign)'s amounts, with their currencies
+///
+/// Parse this from the same response as a `Campaign`, when the campaign might not be in US
+/// dollars.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RawCampaign {
+    /// Registered name of the campaign
+    pub name: String,
+
+    /// The current amount of money raised
+    #[serde(rename = "totalAmountRaised")]
+    pub total_amount_raised: CurrencyAmount,
+
+    /// The goal for money raised
+    pub goal: CurrencyAmount,
+
+    /// The campaign's milestones, in the order the API sent them
+    #[serde(default)]
+    pub milestones: Vec<RawMilestone>,
+}
+
+impl RawCampaign {
+    /// Parse a raw API response, like [`Campaign::from_slice`](crate::Campaign::from_slice)
+    pub fn from_slice(json: &[u8]) -> Result<Self, Report> {
+        serde_json::from_slice::<ApiResponse<RawCampaign>>(json)?.into_campaign()
+    }
+}
+
+/// A [`Milestone`](crate::Milestone)'s amount, with its currency
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RawMilestone {
+    /// A description of an event that the hosts did or will do when the milestone is reached
+    #[serde(rename = "name", default)]
+    pub description: String,
+
+    /// The amount for this milestone, or `None` if it hasn't been set yet
+    #[serde(deserialize_with = "deserialize_nullable")]
+    pub amount: Option<CurrencyAmount>,
+}
+
+/// Like [`CurrencyAmount`], but `{"currency": "USD", "value": null}` is `None`
+fn deserialize_nullable<'de, D>(deserializer: D) -> Result<Option<CurrencyAmount>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Nullable {
+        currency: Currency,
+        #[serde(default, deserialize_with = "deserialize_optional_decimal")]
+        value: Option<String>,
+    }
+
+    let amount = Option::<Nullable>::deserialize(deserializer)?;
+    Ok(amount.and_then(|a| {
+        Some(CurrencyAmount {
+            currency: a.currency,
+            value: a.value?,
+        })
+    }))
+}
+
+fn deserialize_optional_decimal<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Decimal(#[serde(deserialize_with = "deserialize_decimal")] String);
+
+    Ok(Option::<Decimal>::deserialize(deserializer)?.map(|d| d.0))
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+    use crate::Campaign;
+
+    use std::convert::TryFrom;
+
+    const CAD: &str = include_str!("example-response-cad.json");
+
+    #[test]
+    fn keeps_the_currency() {
+        let raw = RawCampaign::from_slice(CAD.as_bytes()).unwrap();
+
+        assert_eq!(raw.name, "Maple Syrup Marathon");
+        assert_eq!(
+            raw.total_amount_raised,
+            CurrencyAmount {
+                currency: Currency::Cad,
+                value: "1234.50".to_string(),
+            }
+        );
+        assert_eq!(raw.goal.to_string(), "5000 CAD");
+        assert_eq!(raw.milestones[1].amount, None);
+        assert_eq!(
+            raw.milestones[2].amount.as_ref().unwrap().currency,
+            Currency::Other("XTS".to_string())
+        );
+
+        let err = raw.total_amount_raised.try_into_usd().unwrap_err();
+        assert_eq!(err.to_string(), "Amount 1234.50 is in CAD, not USD");
+    }
+
+    #[test]
+    fn usd_converts() {
+        let json = include_str!("example-response.json");
+        let raw = RawCampaign::from_slice(json.as_bytes()).unwrap();
+        let campaign = Campaign::from_slice(json.as_bytes()).unwrap();
+
+        assert_eq!(raw.total_amount_raised.currency, Currency::Usd);
+        assert_eq!(
+            raw.total_amount_raised.try_into_usd().unwrap(),
+            campaign.total_amount_raised
+        );
+        for (raw, milestone) in raw.milestones.iter().zip(&campaign.milestones) {
+            let amount = raw.amount.as_ref().map(|a| a.try_into_usd().unwrap());
+            assert_eq!(amount, milestone.amount);
+        }
+
+        let garbage = CurrencyAmount {
+            currency: Currency::Usd,
+            value: "lots".to_string(),
+        };
+        assert!(garbage.try_into_usd().is_err());
+    }
+
+    #[test]
+    fn campaigns_must_be_usd() {
+        let found = |e: Report| e.downcast_ref::<UnsupportedCurrency>().cloned();
+        let cad = Some(UnsupportedCurrency {
+            found: Currency::Cad,
+        });
+
+        assert_eq!(
+            Campaign::from_slice(CAD.as_bytes())
+                .map_err(found)
+                .unwrap_err(),
+            cad
+        );
+        let response: serde_json::Value = serde_json::from_str(CAD).unwrap();
+        assert_eq!(
+            Campaign::try_from(&response).map_err(found).unwrap_err(),
+            cad
+        );
+
+        // One milestone is enough, even one without an amount yet
+        let mut response: serde_json::Value =
+            serde_json::from_str(include_str!("example-response-null-amount.json")).unwrap();
+        response["data"]["campaign"]["milestones"][1]["amount"]["currency"] = "GBP".into();
+        let err = Campaign::try_from(response).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Campaign amounts are in GBP, not USD. Use RawCampaign to read them"
+        );
+
+        // Other failures are left alone
+        let err = Campaign::from_slice(br#"{"data": {"campaign": {}}}"#).unwrap_err();
+        assert!(found(err).is_none());
+
+        let usd: Result<Usd, _> = serde_json::from_str(r#"{"currency": "EUR", "value": "1.00"}"#);
+        assert!(usd.unwrap_err().to_string().contains("in EUR, not USD"));
+    }
+
+    #[test]
+    fn converts() {
+        let mut rates = StaticRates::new();
+        rates.insert(Currency::Gbp, 0.79);
+        rates.insert(Currency::Other("JPY".to_string()), 149.5);
+        rates.insert(Currency::Eur, f64::NAN);
+        rates.insert(Currency::Cad, -1.);
+
+        let raised = Usd::new(22_663.40);
+        let convert = |currency| raised.convert(&rates, currency).map(|a| a.to_string());
+
+        assert_eq!(convert(Currency::Gbp).unwrap(), "17904.09 GBP");
+        assert_eq!(
+            convert(Currency::Other("JPY".to_string())).unwrap(),
+            "3388178.30 JPY"
+        );
+        assert_eq!(convert(Currency::Usd).unwrap(), "22663.40 USD");
+        assert_eq!(convert(Currency::Eur), None);
+        assert_eq!(convert(Currency::Cad), None);
+        assert_eq!(convert(Currency::Other("XTS".to_string())), None);
+
+        // Half a penny rounds to even, like everywhere else
+        assert_eq!(
+            Usd::new(0.01).convert(&rates, Currency::Gbp).unwrap().value,
+            "0.01"
+        );
+        let usd = raised.convert(&rates, Currency::Usd).unwrap();
+        assert_eq!(usd.try_into_usd().unwrap(), raised);
+    }
+
+    #[test]
+    fn round_trips() {
+        let raw = RawCampaign::from_slice(CAD.as_bytes()).unwrap();
+        let json = serde_json::to_string(&raw).unwrap();
+        assert_eq!(serde_json::from_str::<RawCampaign>(&json).unwrap(), raw);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::completion::{CompletionRules, Context};
+use crate::{Campaign, Milestone, Usd};
+
+/// What changed between two snapshots of a campaign, from [`Campaign::diff`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CampaignDiff {
+    /// How much more has been raised, which is negative if the total went down
+    pub raised_delta: Usd,
+
+    /// The goal before and after, if it changed
+    pub goal_changed: Option<(Usd, Usd)>,
+
+    /// Milestones the new total reached that the old one hadn't
+    ///
+    /// This is [`CompletionRules::newly_completed`] with the default rules, so a milestone added
+    /// below the old total doesn't count as crossed.
+    pub milestones_crossed: Vec<Milestone>,
+
+    /// Milestones that weren't there before
+    pub milestones_added: Vec<Milestone>,
+
+    /// Milestones that aren't there any more
+    pub milestones_removed: Vec<Milestone>,
+}
+
+impl CampaignDiff {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.raised_delta == Usd::ZERO
+            && self.goal_changed.is_none()
+            && self.milestones_crossed.is_empty()
+            && self.milestones_added.is_empty()
+            && self.milestones_removed.is_empty()
+    }
+}
+
+impl Campaign {
+    /// What changed since `previous`, an older snapshot of the same campaign
+    ///
+    /// Milestones are matched up by [`id`](Milestone::id), or by description when either side
+    /// doesn't have one, so the order the API lists them in doesn't matter. A milestone without
+    /// an id whose description was edited shows up as one removed and one added.
+    pub fn diff(&self, previous: &Campaign) -> CampaignDiff {
+        let goal_changed = if self.goal == previous.goal {
+            None
+        } else {
+            Some((previous.goal, self.goal))
+        };
+
+        let milestones_crossed = CompletionRules::new()
+            .newly_completed(&Context::new(previous), &Context::new(self))
+            .into_iter()
+            .cloned()
+            .collect();
+
+        CampaignDiff {
+            raised_delta: self.total_amount_raised - previous.total_amount_raised,
+            goal_changed,
+            milestones_crossed,
+            milestones_added: unmatched(&self.milestones, &previous.milestones),
+            milestones_removed: unmatched(&previous.milestones, &self.milestones),
+        }
+    }
+}
+
+/// The milestones in `these` without a match in `others`, pairing duplicates off one for one
+fn unmatched(these: &[Milestone], others: &[Milestone]) -> Vec<Milestone> {
+    let same = |a: &Milestone, b: &Milestone| match (&a.id, &b.id) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.description == b.description,
+    };
+
+    let mut others: Vec<&Milestone> = others.iter().collect();
+    these
+        .iter()
+        .filter(|m| match others.iter().position(|o| same(m, o)) {
+            Some(i) => {
+                others.swap_remove(i);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn example() -> Campaign {
+        Campaign::from_json(include_str!("example-response.json")).unwrap()
+    }
+
+    fn descriptions(milestones: &[Milestone]) -> Vec<&str> {
+        milestones.iter().map(|m| m.description.as_str()).collect()
+    }
+
+    #[test]
+    fn unchanged() {
+        let diff = example().diff(&example());
+        assert!(diff.is_empty(), "{:?}", diff);
+
+        // Reordering milestones isn't a change
+        let mut reordered = example();
+        reordered.milestones.reverse();
+        assert!(reordered.diff(&example()).is_empty());
+    }
+
+    #[test]
+    fn changes() {
+        let previous = example();
+        let mut current = example();
+        current.total_amount_raised = Usd::new(80_000.);
+        current.goal = Usd::new(500_000.);
+        current.milestones.remove(3);
+        current.milestones.insert(
+            0,
+            Milestone {
+                id: None,
+                description: "Myke plays Flight Simulator with a steering wheel".to_string(),
+                amount: Some(Usd::new(60_000.)),
+            },
+        );
+
+        let diff = current.diff(&previous);
+        assert_eq!(diff.raised_delta, Usd::new(57_336.60));
+        assert_eq!(
+            diff.goal_changed,
+            Some((Usd::new(333_333.33), Usd::new(500_000.)))
+        );
+        assert_eq!(
+            descriptions(&diff.milestones_crossed),
+            [
+                "Myke plays Flight Simulator with a steering wheel",
+                "Stephen & Myke go to space via KSP",
+                "Stephen dissembles his NeXTCube on stream",
+            ]
+        );
+        assert_eq!(
+            descriptions(&diff.milestones_added),
+            ["Myke plays Flight Simulator with a steering wheel"]
+        );
+        assert_eq!(
+            descriptions(&diff.milestones_removed),
+            ["$1 million raised in 3 years!"]
+        );
+
+        // Going backwards
+        let diff = previous.diff(&current);
+        assert_eq!(diff.raised_delta, Usd::new(-57_336.60));
+        assert!(diff.milestones_crossed.is_empty());
+        assert_eq!(
+            descriptions(&diff.milestones_added),
+            ["$1 million raised in 3 years!"]
+        );
+    }
+
+    /// Milestones added to the list below the old total weren't crossed by this update
+    #[test]
+    fn added_below_the_total() {
+        let previous = example();
+        let mut current = example();
+        current.milestones.push(Milestone {
+            id: None,
+            description: "Already done".to_string(),
+            amount: Some(Usd::new(1_000.)),
+        });
+
+        let diff = current.diff(&previous);
+        assert!(diff.milestones_crossed.is_empty());
+        assert_eq!(descriptions(&diff.milestones_added), ["Already done"]);
+    }
+
+    /// Edited descriptions are the same milestone, as long as it has an id
+    #[test]
+    fn matches_by_id() {
+        let previous = example();
+        let mut current = example();
+        current.milestones[1].description =
+            "Stephen takes apart his NeXTcube on stream".to_string();
+        assert!(current.diff(&previous).is_empty());
+
+        current.milestones[1].id = None;
+        let diff = current.diff(&previous);
+        assert_eq!(
+            descriptions(&diff.milestones_added),
+            ["Stephen takes apart his NeXTcube on stream"]
+        );
+        assert_eq!(
+            descriptions(&diff.milestones_removed),
+            ["Stephen dissembles his NeXTCube on stream"]
+        );
+    }
+
+    #[test]
+    fn duplicates_pair_off() {
+        let mut previous = example();
+        let mut current = example();
+        let duplicate = previous.milestones[0].clone();
+        previous.milestones.push(duplicate.clone());
+        current.milestones.push(duplicate.clone());
+        current.milestones.push(duplicate);
+
+        let diff = current.diff(&previous);
+        assert_eq!(
+            descriptions(&diff.milestones_added),
+            ["Stephen & Myke go to space via KSP"]
+        );
+        assert!(diff.milestones_removed.is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+//! Human-friendly durations, like `30s`, `5m`, or `1h30m`
+//!
+//! A bare number like `5` is ambiguous, so every part needs a unit. [`HumanDuration`] parses
+//! these with [`FromStr`](std::str::FromStr), so it can be used straight from command line
+//! arguments, and displays them back the same way.
+//!
+//! ```rust
+//! use relay_st_jude::duration::HumanDuration;
+//! use std::time::Duration;
+//!
+//! let parsed: HumanDuration = "1h30m".parse().unwrap();
+//! assert_eq!(parsed.0, Duration::from_secs(90 * 60));
+//! assert_eq!(parsed.to_string(), "1h30m");
+//! ```
+
+use crate::Report;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// What [`HumanDuration`] accepts, for error messages
+pub const GRAMMAR: &str =
+    "one or more whole numbers each followed by a unit, d, h, m, s, or ms, like 30s, 5m, or 1h30m";
+
+/// Each unit, largest first, and its length in milliseconds
+const UNITS: &[(&str, u64)] = &[
+    ("d", 24 * 60 * 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("m", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+/// A [`Duration`] that parses from, and displays as, text like `1h30m`
+///
+/// Displaying uses hours as the largest unit, and drops anything finer than a millisecond. So
+/// everything that was parsed displays as text that parses back to the same duration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HumanDuration(pub Duration);
+
+impl From<Duration> for HumanDuration {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(duration: HumanDuration) -> Self {
+        duration.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Report> {
+        let invalid = |why: &str| {
+            Report::msg(format!(
+                "Invalid duration {:?}: {}. Expected {}",
+                s, why, GRAMMAR
+            ))
+        };
+
+        if s.is_empty() {
+            return Err(invalid("it's empty"));
+        }
+
+        let mut millis: u64 = 0;
+        let mut rest = s;
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if digits == 0 {
+                return Err(invalid("expected a number"));
+            }
+            let (number, after) = rest.split_at(digits);
+
+            let unit_len = after
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(after.len());
+            let (unit, after) = after.split_at(unit_len);
+            let scale = match UNITS.iter().find(|(name, _)| *name == unit) {
+                Some(&(_, scale)) => scale,
+                None if unit.is_empty() => return Err(invalid("the last number has no unit")),
+                None => return Err(invalid(&format!("unknown unit {:?}", unit))),
+            };
+
+            millis = number
+                .parse::<u64>()
+                .ok()
+                .and_then(|n| n.checked_mul(scale))
+                .and_then(|part| millis.checked_add(part))
+                .ok_or_else(|| invalid("it's too long"))?;
+            rest = after;
+        }
+
+        Ok(Self(Duration::from_millis(millis)))
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut millis = self.0.as_millis();
+        if millis == 0 {
+            return f.write_str("0s");
+        }
+
+        // Campaigns run for days, but "48h" reads better than "2d"
+        for &(unit, scale) in UNITS.iter().filter(|(unit, _)| *unit != "d") {
+            let scale = u128::from(scale);
+            if millis >= scale {
+                write!(f, "{}{}", millis / scale, unit)?;
+                millis %= scale;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Duration, String> {
+        s.parse::<HumanDuration>()
+            .map(Duration::from)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn parses() {
+        let secs = Duration::from_secs;
+
+        assert_eq!(parse("30s"), Ok(secs(30)));
+        assert_eq!(parse("5m"), Ok(secs(300)));
+        assert_eq!(parse("1h30m"), Ok(secs(5_400)));
+        assert_eq!(parse("2d"), Ok(secs(172_800)));
+        assert_eq!(parse("1m500ms"), Ok(Duration::from_millis(60_500)));
+        assert_eq!(parse("0s"), Ok(secs(0)));
+        // Out of order and repeated units just add up
+        assert_eq!(parse("30m1h"), Ok(secs(5_400)));
+        assert_eq!(parse("90m"), Ok(secs(5_400)));
+    }
+
+    #[test]
+    fn errors_name_the_grammar() {
+        for bad in &[
+            "",
+            "5",
+            "1h30",
+            "h",
+            "5 m",
+            "1.5h",
+            "3w",
+            "-5m",
+            "99999999999999999999d",
+        ] {
+            let err = parse(bad).unwrap_err();
+            assert!(err.contains(GRAMMAR), "{:?}: {}", bad, err);
+        }
+
+        assert!(parse("1h30").unwrap_err().contains("no unit"));
+        assert!(parse("3w").unwrap_err().contains("unknown unit \"w\""));
+    }
+
+    #[test]
+    fn displays() {
+        let show = |d: Duration| HumanDuration(d).to_string();
+
+        assert_eq!(show(Duration::from_secs(5_400)), "1h30m");
+        assert_eq!(show(Duration::from_secs(48 * 3600)), "48h");
+        assert_eq!(show(Duration::from_secs(90_061)), "25h1m1s");
+        assert_eq!(show(Duration::ZERO), "0s");
+        assert_eq!(show(Duration::from_micros(1_500)), "1ms");
+        assert_eq!(show(Duration::from_micros(999)), "0s");
+    }
+
+    #[test]
+    fn round_trips() {
+        for s in &["30s", "5m", "1h30m", "48h", "25h1m1s", "250ms", "0s"] {
+            let parsed: HumanDuration = s.parse().unwrap();
+            assert_eq!(&parsed.to_string(), s);
+        }
+
+        for &millis in &[1, 999, 1_000, 59_999, 3_600_000, 86_400_001, 123_456_789] {
+            let duration = HumanDuration(Duration::from_millis(millis));
+            assert_eq!(
+                duration.to_string().parse::<HumanDuration>().unwrap(),
+                duration
+            );
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! A small, frozen JSON summary of a campaign, for third-party widgets
+//!
+//! Widget authors (Scriptable, Übersicht, and friends) want a handful of numbers that won't move
+//! around between releases. [`FactSheet`] is that contract. Version 1 is frozen: fields are never
+//! renamed, removed, reordered, or changed in type. Anything new goes in a `"v": 2` sheet
+//! instead.
+//!
+//! ```json
+//! {
+//!   "v": 1,
+//!   "name": "Relay FM for St. Jude 2021",
+//!   "raised_usd": 22663.4,
+//!   "goal_usd": 333333.33,
+//!   "percent": 6.8,
+//!   "next_milestone": {
+//!     "description": "Stephen dissembles his NeXTCube on stream",
+//!     "amount_usd": 55000.0,
+//!     "remaining_usd": 32336.6
+//!   },
+//!   "milestones_completed": 1,
+//!   "milestones_total": 4,
+//!   "campaign_url": "https://tiltify.com/@relay-fm/relay-st-jude-21",
+//!   "fetched_at": "2021-09-17T04:05:06Z"
+//! }
+//! ```
+
+use crate::timestamp::rfc3339;
+use crate::Campaign;
+
+use serde::Serialize;
+
+use std::time::SystemTime;
+
+/// A frozen, versioned summary of a [`Campaign`]
+///
+/// Build one with [`Campaign::fact_sheet`]. The keys serialize in the order they're declared.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct FactSheet {
+    /// The contract version, always `1`
+    pub v: u32,
+
+    /// Registered name of the campaign
+    pub name: String,
+
+    /// The amount raised so far, in USD, rounded to cents
+    pub raised_usd: f64,
+
+    /// The goal, in USD, rounded to cents
+    pub goal_usd: f64,
+
+    /// Progress towards the goal, from 0 to 100, to one decimal place
+    pub percent: f64,
+
+    /// The cheapest milestone not reached yet, or `null` once they're all done
+    pub next_milestone: Option<NextMilestone>,
+
+    /// How many milestones have been reached
+    pub milestones_completed: usize,
+
+    /// How many milestones the campaign has
+    pub milestones_total: usize,
+
+    /// The campaign's public page, when known
+    ///
+    /// This starts out as [`Campaign::url`], which is `null` unless the campaign was fetched by
+    /// vanity and slug. Fill it in with [`FactSheet::source`].
+    pub campaign_url: Option<String>,
+
+    /// When the data was fetched, as an RFC 3339 UTC timestamp like `2021-09-17T04:05:06Z`
+    ///
+    /// Like `campaign_url`, this is `null` until [`FactSheet::source`] fills it in.
+    pub fetched_at: Option<String>,
+}
+
+/// The next milestone to reach, as shown in a [`FactSheet`]
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct NextMilestone {
+    /// What the hosts will do when it's reached
+    pub description: String,
+
+    /// The milestone's amount, in USD
+    pub amount_usd: f64,
+
+    /// How much more needs raising to reach it, in USD
+    pub remaining_usd: f64,
+}
+
+impl FactSheet {
+    /// Record where and when the campaign data came from
+    pub fn source(mut self, campaign_url: impl Into<String>, fetched_at: SystemTime) -> Self {
+        self.campaign_url = Some(campaign_url.into());
+        self.fetched_at = Some(rfc3339(fetched_at));
+        self
+    }
+
+    /// Serialize as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        // Every field is a plain number, string, or Option of those, which can't fail
+        serde_json::to_string_pretty(self).expect("FactSheet always serializes")
+    }
+}
+
+impl Campaign {
+    /// Summarize this campaign as a version 1 [`FactSheet`]
+    pub fn fact_sheet(&self) -> FactSheet {
+        let raised = self.total_amount_raised;
+
+        let next_milestone = self.next_milestone().and_then(|m| {
+            let amount = m.amount?;
+            Some(NextMilestone {
+                description: m.description.clone(),
+                amount_usd: dollars(amount.cents()),
+                remaining_usd: dollars(amount.cents() - raised.cents()),
+            })
+        });
+
+        let percent = (10. * self.percent_raised().0).round() / 10.;
+
+        FactSheet {
+            v: 1,
+            name: self.name.clone(),
+            raised_usd: dollars(raised.cents()),
+            goal_usd: dollars(self.goal.cents()),
+            percent: percent.clamp(0., 100.),
+            next_milestone,
+            milestones_completed: self.milestones_reached().len(),
+            milestones_total: self.milestones.len(),
+            campaign_url: self.url(),
+            fetched_at: None,
+        }
+    }
+}
+
+fn dollars(cents: i64) -> f64 {
+    cents as f64 / 100.
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+    use crate::Usd;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn example() -> Campaign {
+        Campaign::from_json(include_str!("example-response.json")).unwrap()
+    }
+
+    /// The v1 contract. If this test fails, you've broken every widget out there.
+    #[test]
+    fn contract_v1() {
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(1_631_851_506);
+        let sheet = example()
+            .fact_sheet()
+            .source("https://tiltify.com/@relay-fm/relay-st-jude-21", fetched_at);
+
+        assert_eq!(
+            sheet.to_json(),
+            indoc::indoc!(
+                r#"
+                {
+                  "v": 1,
+                  "name": "Relay FM for St. Jude 2021",
+                  "raised_usd": 22663.4,
+                  "goal_usd": 333333.33,
+                  "percent": 6.8,
+                  "next_milestone": {
+                    "description": "Stephen dissembles his NeXTCube on stream",
+                    "amount_usd": 55000.0,
+                    "remaining_usd": 32336.6
+                  },
+                  "milestones_completed": 1,
+                  "milestones_total": 4,
+                  "campaign_url": "https://tiltify.com/@relay-fm/relay-st-jude-21",
+                  "fetched_at": "2021-09-17T04:05:06Z"
+                }"#
+            )
+        );
+    }
+
+    #[test]
+    fn unsourced_fields_are_null() {
+        let sheet = example().fact_sheet();
+        let json: serde_json::Value = serde_json::from_str(&sheet.to_json()).unwrap();
+
+        assert!(json["campaign_url"].is_null());
+        assert!(json["fetched_at"].is_null());
+    }
+
+    #[test]
+    fn url_from_the_campaign() {
+        let mut campaign = example();
+        campaign.page = Some(crate::CampaignPage::new("@relay-fm", "relay-st-jude-21"));
+        let sheet = campaign.fact_sheet();
+
+        assert_eq!(
+            sheet.campaign_url.as_deref(),
+            Some("https://tiltify.com/@relay-fm/relay-st-jude-21")
+        );
+        assert_eq!(sheet.fetched_at, None);
+    }
+
+    #[test]
+    fn every_milestone_done() {
+        let mut campaign = example();
+        campaign.total_amount_raised = Usd::new(400_000.);
+        let sheet = campaign.fact_sheet();
+
+        assert_eq!(sheet.next_milestone, None);
+        assert_eq!(sheet.milestones_completed, 4);
+        assert_eq!(sheet.percent, 100.);
+    }
+
+    #[test]
+    fn unpriced_milestones_are_not_counted_as_done() {
+        let campaign =
+            Campaign::from_json(include_str!("example-response-null-amount.json")).unwrap();
+        let sheet = campaign.fact_sheet();
+
+        assert_eq!(sheet.milestones_completed, 1);
+        assert_eq!(sheet.milestones_total, 3);
+        assert_eq!(sheet.next_milestone.unwrap().amount_usd, 55_000.);
+    }
+}
@@ -0,0 +1,510 @@
+//! A minimal C-compatible interface to the campaign model
+//!
+//! Everything here is `extern "C"` and only deals in C strings, doubles, and opaque pointers, so
+//! it can be called from Swift, C, or anything else with a C FFI. A header is checked in at
+//! `include/relay_st_jude.h` and can be regenerated with `cbindgen --config cbindgen.toml`.
+//!
+//! A plain `cargo build` only makes the Rust library. Build the shared or static C library with
+//! `cargo rustc`, which can override the crate type for just that invocation:
+//!
+//! ```text
+//! cargo rustc --lib --release --features ffi --crate-type cdylib
+//! cargo rustc --lib --release --features ffi --crate-type staticlib
+//! ```
+//!
+//! Some ground rules:
+//! - Every pointer argument is null-checked. Nothing here will dereference a null pointer.
+//! - Strings returned from this module are owned by the caller and **must** be released with
+//!   [`rsj_string_free`]. Campaigns must be released with [`rsj_campaign_free`].
+//! - Freeing the same pointer twice is caught and reported instead of corrupting the heap.
+//! - Panics never cross the boundary. They're reported as [`RsjStatus::Panic`].
+//! - When something fails, [`rsj_last_error_message`] describes what happened on this thread.
+
+use crate::Campaign;
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::Mutex;
+
+/// Status codes returned by the fallible functions in this module
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RsjStatus {
+    /// Everything went fine
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// Fetching or parsing the campaign failed
+    Fetch = 3,
+    /// A milestone index was past the end of the list
+    OutOfBounds = 4,
+    /// The pointer was not a live value from this library, e.g. it was already freed
+    InvalidHandle = 5,
+    /// The library panicked. This is a bug!
+    Panic = 6,
+}
+
+/// An opaque handle to a fetched [`Campaign`]
+pub struct RsjCampaign {
+    campaign: Campaign,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Every pointer we've handed out and not yet seen freed. This is what lets us catch double frees
+// and use-after-frees instead of hoping for the best.
+static LIVE_CAMPAIGNS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+static LIVE_STRINGS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+fn set_last_error(message: impl Into<String>) {
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message.into()));
+}
+
+fn fail(status: RsjStatus, message: impl Into<String>) -> RsjStatus {
+    set_last_error(message);
+    status
+}
+
+/// Run `f`, turning a panic into [`RsjStatus::Panic`]
+fn guard(f: impl FnOnce() -> RsjStatus) -> RsjStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(status) => status,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            fail(RsjStatus::Panic, format!("panic: {}", message))
+        }
+    }
+}
+
+/// Like [`guard`], but for functions that hand back a pointer and use null for failure
+fn guard_ptr<T>(f: impl FnOnce() -> Result<*mut T, RsjStatus>) -> *mut T {
+    let mut out = ptr::null_mut();
+    guard(|| match f() {
+        Ok(p) => {
+            out = p;
+            RsjStatus::Ok
+        }
+        Err(status) => status,
+    });
+    out
+}
+
+unsafe fn str_arg<'a>(s: *const c_char, name: &str) -> Result<&'a str, RsjStatus> {
+    if s.is_null() {
+        return Err(fail(RsjStatus::NullPointer, format!("{} was null", name)));
+    }
+    CStr::from_ptr(s).to_str().map_err(|e| {
+        fail(
+            RsjStatus::InvalidUtf8,
+            format!("{} was not UTF-8: {}", name, e),
+        )
+    })
+}
+
+fn campaign_arg<'a>(campaign: *const RsjCampaign) -> Result<&'a Campaign, RsjStatus> {
+    if campaign.is_null() {
+        return Err(fail(RsjStatus::NullPointer, "campaign was null"));
+    }
+    if !LIVE_CAMPAIGNS
+        .lock()
+        .unwrap()
+        .contains(&(campaign as usize))
+    {
+        return Err(fail(
+            RsjStatus::InvalidHandle,
+            "campaign is not a live campaign (was it already freed?)",
+        ));
+    }
+
+    // Safety: We only put pointers from `Box::into_raw` in LIVE_CAMPAIGNS, and remove them before
+    // they're freed.
+    Ok(unsafe { &(*campaign).campaign })
+}
+
+fn into_campaign_ptr(campaign: Campaign) -> *mut RsjCampaign {
+    let p = Box::into_raw(Box::new(RsjCampaign { campaign }));
+    LIVE_CAMPAIGNS.lock().unwrap().insert(p as usize);
+    p
+}
+
+fn into_string_ptr(s: &str) -> *mut c_char {
+    // Interior nuls can't be represented in a C string, so drop them rather than fail
+    let s = CString::new(s.replace('\0', "")).unwrap();
+    let p = s.into_raw();
+    LIVE_STRINGS.lock().unwrap().insert(p as usize);
+    p
+}
+
+unsafe fn write_out<T>(out: *mut T, value: T) -> RsjStatus {
+    if out.is_null() {
+        return fail(RsjStatus::NullPointer, "output pointer was null");
+    }
+    *out = value;
+    RsjStatus::Ok
+}
+
+/// Fetch a campaign by vanity & slug from online
+///
+/// Returns null on failure; call [`rsj_last_error_message`] for details.
+/// Free the result with [`rsj_campaign_free`].
+///
+/// # Safety
+/// `vanity` and `slug` must each be null or point to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_fetch(vanity: *const c_char, slug: *const c_char) -> *mut RsjCampaign {
+    guard_ptr(|| {
+        let vanity = str_arg(vanity, "vanity")?;
+        let slug = str_arg(slug, "slug")?;
+
+        Campaign::fetch_by(vanity, slug)
+            .map(into_campaign_ptr)
+            .map_err(|e| fail(RsjStatus::Fetch, format!("{}", e)))
+    })
+}
+
+/// Parse a campaign from a saved API response, without touching the network
+///
+/// Returns null on failure; call [`rsj_last_error_message`] for details.
+/// Free the result with [`rsj_campaign_free`].
+///
+/// # Safety
+/// `json` must be null or point to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_from_json(json: *const c_char) -> *mut RsjCampaign {
+    guard_ptr(|| {
+        let json = str_arg(json, "json")?;
+
+        Campaign::from_json(json)
+            .map(into_campaign_ptr)
+            .map_err(|e| fail(RsjStatus::Fetch, format!("{}", e)))
+    })
+}
+
+/// Free a campaign returned by [`rsj_fetch`] or [`rsj_campaign_from_json`]
+///
+/// Passing null is a no-op. Passing an already-freed campaign returns
+/// [`RsjStatus::InvalidHandle`] and leaves memory alone.
+///
+/// # Safety
+/// `campaign` must be null or a pointer previously returned by this library.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_free(campaign: *mut RsjCampaign) -> RsjStatus {
+    guard(|| {
+        if campaign.is_null() {
+            return RsjStatus::Ok;
+        }
+        if !LIVE_CAMPAIGNS.lock().unwrap().remove(&(campaign as usize)) {
+            return fail(
+                RsjStatus::InvalidHandle,
+                "campaign is not a live campaign (was it already freed?)",
+            );
+        }
+
+        drop(Box::from_raw(campaign));
+        RsjStatus::Ok
+    })
+}
+
+/// The campaign's name as a UTF-8 string
+///
+/// Returns null on failure. Free the result with [`rsj_string_free`].
+///
+/// # Safety
+/// `campaign` must be null or a pointer previously returned by this library.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_name(campaign: *const RsjCampaign) -> *mut c_char {
+    guard_ptr(|| Ok(into_string_ptr(&campaign_arg(campaign)?.name)))
+}
+
+/// The amount raised so far, in USD
+///
+/// # Safety
+/// `campaign` must be null or a pointer previously returned by this library, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_raised(
+    campaign: *const RsjCampaign,
+    out: *mut f64,
+) -> RsjStatus {
+    guard(|| match campaign_arg(campaign) {
+        Ok(c) => write_out(out, c.total_amount_raised.usd()),
+        Err(status) => status,
+    })
+}
+
+/// The campaign's goal, in USD
+///
+/// # Safety
+/// `campaign` must be null or a pointer previously returned by this library, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_goal(
+    campaign: *const RsjCampaign,
+    out: *mut f64,
+) -> RsjStatus {
+    guard(|| match campaign_arg(campaign) {
+        Ok(c) => write_out(out, c.goal.usd()),
+        Err(status) => status,
+    })
+}
+
+/// How many milestones the campaign has
+///
+/// # Safety
+/// `campaign` must be null or a pointer previously returned by this library, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_milestone_count(
+    campaign: *const RsjCampaign,
+    out: *mut usize,
+) -> RsjStatus {
+    guard(|| match campaign_arg(campaign) {
+        Ok(c) => write_out(out, c.milestones.len()),
+        Err(status) => status,
+    })
+}
+
+/// The description and amount of the milestone at `index`
+///
+/// Milestones are in the order the API returned them. On success, `*out_description` must be
+/// freed with [`rsj_string_free`]. `*out_amount` is NaN for a milestone without an amount yet.
+///
+/// # Safety
+/// `campaign` must be null or a pointer previously returned by this library, and the out pointers
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_campaign_milestone_at(
+    campaign: *const RsjCampaign,
+    index: usize,
+    out_description: *mut *mut c_char,
+    out_amount: *mut f64,
+) -> RsjStatus {
+    guard(|| {
+        let c = match campaign_arg(campaign) {
+            Ok(c) => c,
+            Err(status) => return status,
+        };
+        if out_description.is_null() || out_amount.is_null() {
+            return fail(RsjStatus::NullPointer, "output pointer was null");
+        }
+        let milestone = match c.milestones.get(index) {
+            Some(m) => m,
+            None => {
+                return fail(
+                    RsjStatus::OutOfBounds,
+                    format!(
+                        "milestone index {} out of bounds for {} milestones",
+                        index,
+                        c.milestones.len()
+                    ),
+                )
+            }
+        };
+
+        *out_description = into_string_ptr(&milestone.description);
+        *out_amount = milestone.amount.map_or(f64::NAN, |a| a.usd());
+        RsjStatus::Ok
+    })
+}
+
+/// A description of the most recent error on this thread
+///
+/// Returns null if nothing has failed yet. Free the result with [`rsj_string_free`].
+#[no_mangle]
+pub extern "C" fn rsj_last_error_message() -> *mut c_char {
+    guard_ptr(|| {
+        Ok(LAST_ERROR.with(|e| match &*e.borrow() {
+            Some(message) => into_string_ptr(message),
+            None => ptr::null_mut(),
+        }))
+    })
+}
+
+/// Free a string returned by this library
+///
+/// Passing null is a no-op. Passing an already-freed string returns
+/// [`RsjStatus::InvalidHandle`] and leaves memory alone.
+///
+/// # Safety
+/// `s` must be null or a pointer previously returned by this library.
+#[no_mangle]
+pub unsafe extern "C" fn rsj_string_free(s: *mut c_char) -> RsjStatus {
+    guard(|| {
+        if s.is_null() {
+            return RsjStatus::Ok;
+        }
+        if !LIVE_STRINGS.lock().unwrap().remove(&(s as usize)) {
+            return fail(
+                RsjStatus::InvalidHandle,
+                "string was not returned by this library (was it already freed?)",
+            );
+        }
+
+        drop(CString::from_raw(s));
+        RsjStatus::Ok
+    })
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    const RESPONSE: &str = include_str!("example-response.json");
+
+    /// Read (and free) the last error, like a foreign caller would
+    fn last_error() -> String {
+        let p = rsj_last_error_message();
+        assert!(!p.is_null());
+
+        let message = unsafe { CStr::from_ptr(p) }.to_str().unwrap().to_string();
+        assert_eq!(unsafe { rsj_string_free(p) }, RsjStatus::Ok);
+
+        message
+    }
+
+    fn load_example() -> *mut RsjCampaign {
+        let json = CString::new(RESPONSE).unwrap();
+        let campaign = unsafe { rsj_campaign_from_json(json.as_ptr()) };
+        assert!(!campaign.is_null());
+
+        campaign
+    }
+
+    #[test]
+    fn accessors() {
+        let campaign = load_example();
+
+        unsafe {
+            let name = rsj_campaign_name(campaign);
+            assert_eq!(
+                CStr::from_ptr(name).to_str().unwrap(),
+                "Relay FM for St. Jude 2021"
+            );
+            assert_eq!(rsj_string_free(name), RsjStatus::Ok);
+
+            let mut raised = 0.;
+            assert_eq!(rsj_campaign_raised(campaign, &mut raised), RsjStatus::Ok);
+            assert_eq!(raised, 22_663.40);
+
+            let mut goal = 0.;
+            assert_eq!(rsj_campaign_goal(campaign, &mut goal), RsjStatus::Ok);
+            assert_eq!(goal, 333_333.33);
+
+            let mut count = 0;
+            assert_eq!(
+                rsj_campaign_milestone_count(campaign, &mut count),
+                RsjStatus::Ok
+            );
+            assert_eq!(count, 4);
+
+            let mut description = ptr::null_mut();
+            let mut amount = 0.;
+            assert_eq!(
+                rsj_campaign_milestone_at(campaign, 1, &mut description, &mut amount),
+                RsjStatus::Ok
+            );
+            assert_eq!(
+                CStr::from_ptr(description).to_str().unwrap(),
+                "Stephen dissembles his NeXTCube on stream"
+            );
+            assert_eq!(amount, 55_000.00);
+            assert_eq!(rsj_string_free(description), RsjStatus::Ok);
+
+            assert_eq!(rsj_campaign_free(campaign), RsjStatus::Ok);
+        }
+    }
+
+    #[test]
+    fn null_and_bad_arguments() {
+        unsafe {
+            assert!(rsj_fetch(ptr::null(), ptr::null()).is_null());
+            assert_eq!(last_error(), "vanity was null");
+
+            let bad_utf8 = [0xffu8, 0];
+            let slug = CString::new("relay-st-jude-21").unwrap();
+            assert!(rsj_fetch(bad_utf8.as_ptr() as *const c_char, slug.as_ptr()).is_null());
+            assert!(last_error().starts_with("vanity was not UTF-8"));
+
+            let mut out = 0.;
+            assert_eq!(
+                rsj_campaign_raised(ptr::null(), &mut out),
+                RsjStatus::NullPointer
+            );
+
+            let campaign = load_example();
+            assert_eq!(
+                rsj_campaign_goal(campaign, ptr::null_mut()),
+                RsjStatus::NullPointer
+            );
+
+            let mut description = ptr::null_mut();
+            assert_eq!(
+                rsj_campaign_milestone_at(campaign, 4, &mut description, &mut out),
+                RsjStatus::OutOfBounds
+            );
+            assert!(description.is_null());
+            assert_eq!(
+                last_error(),
+                "milestone index 4 out of bounds for 4 milestones"
+            );
+
+            assert_eq!(rsj_campaign_free(campaign), RsjStatus::Ok);
+            assert_eq!(rsj_campaign_free(ptr::null_mut()), RsjStatus::Ok);
+            assert_eq!(rsj_string_free(ptr::null_mut()), RsjStatus::Ok);
+        }
+    }
+
+    #[test]
+    fn graph_ql_errors_are_reported() {
+        let json = CString::new(r#"{"data": null, "errors": [{"message": "nope"}]}"#).unwrap();
+
+        let campaign = unsafe { rsj_campaign_from_json(json.as_ptr()) };
+        assert!(campaign.is_null());
+        assert_eq!(last_error(), "Campaign Query failed:\n~ nope");
+    }
+
+    #[test]
+    fn double_free_is_caught() {
+        let campaign = load_example();
+
+        unsafe {
+            let name = rsj_campaign_name(campaign);
+            assert_eq!(rsj_string_free(name), RsjStatus::Ok);
+            assert_eq!(rsj_string_free(name), RsjStatus::InvalidHandle);
+
+            assert_eq!(rsj_campaign_free(campaign), RsjStatus::Ok);
+            assert_eq!(rsj_campaign_free(campaign), RsjStatus::InvalidHandle);
+            assert!(last_error().contains("already freed"));
+
+            // Use after free is caught the same way
+            assert!(rsj_campaign_name(campaign).is_null());
+            let mut out = 0;
+            assert_eq!(
+                rsj_campaign_milestone_count(campaign, &mut out),
+                RsjStatus::InvalidHandle
+            );
+        }
+    }
+
+    #[test]
+    fn panics_stay_inside() {
+        assert_eq!(guard(|| panic!("oh no")), RsjStatus::Panic);
+        assert_eq!(last_error(), "panic: oh no");
+
+        let p: *mut RsjCampaign = guard_ptr(|| panic!("{}", "formatted"));
+        assert!(p.is_null());
+        assert_eq!(last_error(), "panic: formatted");
+    }
+}
@@ -0,0 +1,261 @@
+//! Finding room for new milestones
+//!
+//! Hosts add milestones on the fly, and the question is always "what should the next one be?".
+//! These helpers look at where the existing milestones, the total, and the goal sit, and suggest
+//! round numbers in the emptiest stretches.
+
+use crate::{Campaign, Usd};
+
+/// A stretch between two interesting amounts in a campaign: milestones, the total raised, or the
+/// goal
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MilestoneGap {
+    /// The lower end of the gap
+    pub start: Usd,
+
+    /// The upper end of the gap
+    pub end: Usd,
+}
+
+impl MilestoneGap {
+    /// How much money the gap spans
+    pub fn size(&self) -> Usd {
+        Usd::new(self.end.usd() - self.start.usd())
+    }
+}
+
+/// A proposed milestone amount, and the gap it was chosen to fill
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MilestoneSuggestion {
+    /// The suggested milestone amount
+    pub amount: Usd,
+
+    /// The gap this splits, before the suggestion was made
+    pub gap: MilestoneGap,
+}
+
+impl Campaign {
+    /// Every gap between consecutive milestones, the total raised, and the goal, in ascending
+    /// order
+    ///
+    /// Amounts that appear more than once (say, a milestone set at the goal) only count once, so
+    /// there are no empty gaps.
+    pub fn milestone_gaps(&self) -> Vec<MilestoneGap> {
+        let mut points: Vec<f64> = self
+            .milestones
+            .iter()
+            .filter_map(|m| m.amount.map(|a| a.usd()))
+            .chain([self.total_amount_raised.usd(), self.goal.usd()])
+            .collect();
+        points.sort_by(f64::total_cmp);
+        points.dedup();
+
+        points
+            .windows(2)
+            .map(|w| MilestoneGap {
+                start: Usd::new(w[0]),
+                end: Usd::new(w[1]),
+            })
+            .collect()
+    }
+
+    /// Suggest up to `count` round-number milestone amounts, filling the largest gaps first
+    ///
+    /// Suggestions are never at or below the current total, so there may be fewer than `count`
+    /// when the campaign has passed its goal and its last milestone. Each suggestion splits its
+    /// gap, so asking for several won't stack them all in the same place. The results are sorted
+    /// by amount.
+    pub fn suggest_milestones(&self, count: usize) -> Vec<MilestoneSuggestion> {
+        let raised = self.total_amount_raised.usd();
+
+        // Only the part of each gap above the total is up for grabs
+        let mut open: Vec<MilestoneGap> = self
+            .milestone_gaps()
+            .into_iter()
+            .filter(|gap| gap.end.usd() > raised)
+            .map(|gap| MilestoneGap {
+                start: Usd::new(gap.start.usd().max(raised)),
+                end: gap.end,
+            })
+            .collect();
+
+        let mut suggestions = vec![];
+        while suggestions.len() < count {
+            // Biggest gap wins, and the lowest one breaks ties so that results are deterministic
+            let largest = open.iter().enumerate().max_by(|(_, a), (_, b)| {
+                a.size()
+                    .usd()
+                    .total_cmp(&b.size().usd())
+                    .then(b.start.usd().total_cmp(&a.start.usd()))
+            });
+            let (i, gap) = match largest {
+                Some((i, gap)) => (i, *gap),
+                None => break,
+            };
+
+            // Anywhere in the middle half of the gap is fine
+            let amount = round_to_nice(
+                (gap.start.usd() + gap.end.usd()) / 2.,
+                gap.size().usd() / 4.,
+            );
+            if amount <= gap.start.usd() || amount >= gap.end.usd() {
+                // Too small to split into anything round. Nothing left will be bigger.
+                break;
+            }
+
+            open.swap_remove(i);
+            open.push(MilestoneGap {
+                start: gap.start,
+                end: Usd::new(amount),
+            });
+            open.push(MilestoneGap {
+                start: Usd::new(amount),
+                end: gap.end,
+            });
+
+            suggestions.push(MilestoneSuggestion {
+                amount: Usd::new(amount),
+                gap,
+            });
+        }
+
+        suggestions.sort_by_key(|s| s.amount);
+        suggestions
+    }
+}
+
+/// The roundest number within `tolerance` of `amount`
+///
+/// "Roundest" means a multiple of the biggest step from the 1-2-5 series (…, $10k, $20k, $50k,
+/// $100k, …) that has a multiple in range, never going finer than whole dollars: $65,000 rather
+/// than $64,213.17.
+pub(crate) fn round_to_nice(amount: f64, tolerance: f64) -> f64 {
+    let mut magnitude = 10f64.powi(amount.abs().max(1.).log10().ceil() as i32);
+
+    while magnitude >= 1. {
+        for step in [magnitude, magnitude / 2., magnitude / 5.] {
+            let rounded = (amount / step).round() * step;
+            if step >= 1. && (rounded - amount).abs() <= tolerance {
+                return rounded;
+            }
+        }
+        magnitude /= 10.;
+    }
+
+    amount.round()
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+    use crate::Milestone;
+
+    fn campaign(raised: f64, goal: f64, milestones: &[f64]) -> Campaign {
+        milestones
+            .iter()
+            .fold(
+                Campaign::builder().name("Test").raised(raised).goal(goal),
+                |builder, &amount| builder.milestone(format!("${}", amount), amount),
+            )
+            .build()
+    }
+
+    /// The ladder from the example response
+    fn example() -> Campaign {
+        campaign(
+            22_663.40,
+            333_333.33,
+            &[75_000., 55_000., 20_000., 196_060.44],
+        )
+    }
+
+    fn amounts(suggestions: &[MilestoneSuggestion]) -> Vec<f64> {
+        suggestions.iter().map(|s| s.amount.usd()).collect()
+    }
+
+    #[test]
+    fn gaps_are_sorted_and_cover_everything() {
+        let gaps: Vec<(f64, f64)> = example()
+            .milestone_gaps()
+            .iter()
+            .map(|g| (g.start.usd(), g.end.usd()))
+            .collect();
+
+        assert_eq!(
+            gaps,
+            [
+                (20_000., 22_663.40),
+                (22_663.40, 55_000.),
+                (55_000., 75_000.),
+                (75_000., 196_060.44),
+                (196_060.44, 333_333.33),
+            ]
+        );
+    }
+
+    #[test]
+    fn suggestions_for_example_ladder() {
+        let suggestions = example().suggest_milestones(3);
+
+        assert_eq!(amounts(&suggestions), [150_000., 250_000., 300_000.]);
+
+        // The $300k suggestion came from splitting the gap the $250k one left behind
+        assert_eq!(suggestions[2].gap.start, Usd::new(250_000.));
+        assert_eq!(suggestions[2].gap.end, Usd::new(333_333.33));
+    }
+
+    #[test]
+    fn suggestions_stay_above_total() {
+        let suggestions = campaign(60_000., 100_000., &[50_000., 75_000.]).suggest_milestones(5);
+
+        assert!(amounts(&suggestions).iter().all(|&a| a > 60_000.));
+        assert_eq!(
+            amounts(&suggestions),
+            [65_000., 70_000., 80_000., 85_000., 90_000.]
+        );
+    }
+
+    #[test]
+    fn degenerate_ladders() {
+        // No milestones: just the stretch from the total to the goal
+        let empty = campaign(1_000., 10_000., &[]);
+        assert_eq!(empty.milestone_gaps().len(), 1);
+        assert_eq!(amounts(&empty.suggest_milestones(1)), [5_000.]);
+
+        // One milestone, at the goal
+        let single = campaign(0., 10_000., &[10_000.]);
+        assert_eq!(single.milestone_gaps().len(), 1);
+        assert_eq!(amounts(&single.suggest_milestones(2)), [2_000., 5_000.]);
+
+        // Past everything: nowhere to put a milestone
+        let done = campaign(20_000., 10_000., &[5_000.]);
+        assert_eq!(done.milestone_gaps().len(), 2);
+        assert_eq!(done.suggest_milestones(3), []);
+
+        assert_eq!(example().suggest_milestones(0), []);
+    }
+
+    #[test]
+    fn nice_numbers() {
+        assert_eq!(round_to_nice(64_213.17, 1_000.), 65_000.);
+        assert_eq!(round_to_nice(64_213.17, 500.), 64_000.);
+        assert_eq!(round_to_nice(64_213.17, 20_000.), 50_000.);
+        assert_eq!(round_to_nice(135_530.22, 30_265.), 150_000.);
+        assert_eq!(round_to_nice(5_000., 1_250.), 5_000.);
+        assert_eq!(round_to_nice(12.34, 0.5), 12.);
+        assert_eq!(round_to_nice(0.4, 0.1), 0.);
+    }
+
+    /// Milestones without an amount aren't anywhere on the ladder
+    #[test]
+    fn unpriced_milestones_are_ignored() {
+        let mut with_unpriced = example();
+        with_unpriced.milestones.push(Milestone {
+            id: None,
+            description: "Not priced yet".to_string(),
+            amount: None,
+        });
+
+        assert_eq!(with_unpriced.milestone_gaps(), example().milestone_gaps());
+    }
+}
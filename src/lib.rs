@@ -15,105 +15,831 @@
 //! let campaign: Campaign = Campaign::fetch().unwrap();
 //!
 //! // Currency amounts come in USD
-//! let current: f64 = campaign.total_amount_raised.usd();
-//! let goal: f64 = campaign.goal.usd();
+//! println!("{} of {} raised", campaign.total_amount_raised, campaign.goal);
 //!
 //! // Do something interesting with the data!
-//! println!("${:.2}% raised so far!", 100. * current / goal );
+//! println!("{} raised so far!", campaign.percent_raised());
 //! ```
+//!
+//! Nothing but [`Campaign::fetch`] and the [`relay`] module is specific to Relay. Any other
+//! Tiltify campaign works the same way:
+//! ```rust,no_run
+//! use relay_st_jude::{Campaign, ListOptions};
+//!
+//! let campaign = Campaign::fetch_by("@my-team", "my-campaign")?;
+//! println!("{}: {} of {}", campaign.name, campaign.total_amount_raised, campaign.goal);
+//!
+//! for milestone in campaign.milestone_page(&ListOptions::default()).milestones {
+//!     println!("    {}", milestone.description);
+//! }
+//! # Ok::<(), relay_st_jude::Report>(())
+//! ```
+//!
+//! For the common types in one import, use the [`prelude`]:
+//! ```rust
+//! use relay_st_jude::prelude::*;
+//! ```
+//!
+//! # Types
+//! - The campaign model: [`Campaign`], [`CampaignId`], [`CampaignStatus`], and [`Milestone`]
+//! - Fetching only what you need: [`CampaignQuery`] and [`OutputKind`]
+//! - Money: [`Usd`], [`RoundingPolicy`], [`ParseUsdError`], and [`LocalizedUsd`]
+//! - Other currencies: [`RawCampaign`] keeps each [`CurrencyAmount`]'s [`Currency`], and
+//!   [`Usd::convert`] uses your [`ExchangeRates`] to show amounts in them
+//! - Progress: [`Campaign::percent_raised`] and [`Milestone::percent_of`], as a [`Percent`], and
+//!   [`Campaign::amount_remaining`]
+//! - Widgets: [`FactSheet`], a small JSON summary with a frozen format
+//! - Archiving: [`Campaign::to_canonical_json`], JSON that diffs cleanly
+//! - Planning new milestones: [`MilestoneGap`] and [`MilestoneSuggestion`]
+//! - Listing milestones: [`Campaign::milestone_page`] with [`ListOptions`]
+//! - Errors: [`Report`], from [`color_eyre`], is returned by everything that can fail
+//! - [`completion`]: Custom rules for when a milestone counts as completed
+//! - [`duration`]: Durations written like `1h30m`
+//! - [`overlay`]: Frames for animated browser-source overlays
+//! - [`template`]: `{variable}` templates for announcement messages
+//! - [`text`]: Display-width math for lining up terminal output
+//! - [`simulate`]: "What if" projections of when milestones will be reached
+//! - [`relay`]: The Relay FM for St. Jude campaign, and the only Relay-specific code
+//! - [`selftest`]: Offline checks, used by `relay-st-jude --selftest`
+//! - `ffi`: C bindings, with the `ffi` feature
 
 #![warn(missing_docs)]
 
-use color_eyre::Report;
-use num_format::{Locale, ToFormattedString};
-use serde::{de, Deserialize, Deserializer};
-use serde_json::json;
+/// The error type returned throughout this crate
+///
+/// This is re-exported so that you don't need to depend on the exact same version of
+/// `color-eyre` to name it.
+pub use color_eyre::Report;
+pub use num_format::Locale;
+use num_format::ToFormattedString;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
+use std::convert::TryFrom;
 use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+mod avatar;
+pub use avatar::Avatar;
+
+mod builder;
+pub use builder::CampaignBuilder;
+
+mod canonical;
+
+mod diff;
+pub use diff::CampaignDiff;
+
+mod fact_sheet;
+pub use fact_sheet::{FactSheet, NextMilestone};
+
+mod gaps;
+pub use gaps::{MilestoneGap, MilestoneSuggestion};
+
+mod listing;
+pub use listing::{CompletedPlacement, ListOptions, MilestonePage, MilestoneSort};
+
+mod page;
+pub use page::CampaignPage;
+
+mod progress;
+pub use progress::Percent;
+
+mod reward;
+pub use reward::Reward;
+
+mod summary;
+pub use summary::{CampaignSummary, MilestoneDisplay};
+
+mod timestamp;
+
+mod currency;
+pub use currency::{
+    Currency, CurrencyAmount, ExchangeRates, RawCampaign, RawMilestone, StaticRates,
+    UnsupportedCurrency,
+};
+
+pub mod completion;
+pub mod duration;
+pub mod prelude;
+pub mod relay;
+
+pub mod overlay;
+pub mod template;
+pub mod text;
+
+mod ops;
+use ops::Operation;
+pub use ops::{CampaignQuery, OutputKind};
+
+mod query;
+#[doc(hidden)]
+pub use query::bench;
+use query::RequestBody;
+
+pub mod selftest;
+pub mod simulate;
+
+mod strict;
 
 /// A fund-raising milestone
 ///
 /// New events are unlocked when this milestone is reached. Check
 /// [`Milestone::description`](crate::Milestone::description) for details on the event.
-#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Milestone {
+    /// Stable id of the milestone
+    ///
+    /// Prefer this over the description to follow a milestone between polls. It's optional so
+    /// that responses saved before we asked for it still parse.
+    #[serde(default)]
+    pub id: Option<MilestoneId>,
+
     /// A description of an event that the hosts did or will do when the milestone is reached
-    #[serde(rename = "name")]
+    ///
+    /// Empty if it was fetched with [`CampaignQuery::Amounts`].
+    #[serde(rename = "name", default)]
     pub description: String,
 
     /// The amount, in USD, for this milestone
-    pub amount: Usd,
+    ///
+    /// This is `None` when organizers saved the milestone before setting its amount. Those are
+    /// left out of progress math, and shown last.
+    #[serde(
+        deserialize_with = "deserialize_nullable_amount",
+        serialize_with = "serialize_nullable_amount"
+    )]
+    pub amount: Option<Usd>,
 }
 
 /// A dollar amount expressed in United States Dollar (USD)
-#[derive(Deserialize, Copy, Clone, Debug, PartialEq, PartialOrd)]
+///
+/// This is a whole number of cents, so sums and differences are exact, and `Usd` can be a
+/// `HashMap`, `BTreeMap`, or `sort_by_key` key directly. Equal amounts hash the same however
+/// they were made. Dollar amounts given as an `f64` are rounded to the nearest cent with the
+/// default [`RoundingPolicy`].
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
+#[serde(try_from = "UsdFields")]
 pub struct Usd {
     // TODO: We'd like to make this a tuple-struct, but need to sort out the serde logic first
-    #[serde(deserialize_with = "deserialize_f64_from_str", rename = "value")]
-    amount: f64,
+    cents: i64,
+}
+
+/// A [`Usd`] as the API sends it, before checking the currency
+#[derive(Deserialize)]
+struct UsdFields {
+    #[serde(default)]
+    currency: Option<Currency>,
+
+    #[serde(deserialize_with = "deserialize_cents_from_str")]
+    value: i64,
+}
+
+impl TryFrom<UsdFields> for Usd {
+    type Error = UnsupportedCurrency;
+
+    fn try_from(fields: UsdFields) -> Result<Self, UnsupportedCurrency> {
+        UnsupportedCurrency::check(fields.currency)?;
+        Ok(Usd::from_cents(fields.value))
+    }
 }
 
 impl Usd {
-    /// Construct from a dollar amount
+    /// $0.00, which is also what summing no amounts gives
+    pub const ZERO: Usd = Usd::from_cents(0);
+
+    /// The largest amount, `i64::MAX` cents, about $92 quadrillion
+    pub const MAX: Usd = Usd::from_cents(i64::MAX);
+
+    /// The smallest amount, `i64::MIN` cents, about -$92 quadrillion
+    pub const MIN: Usd = Usd::from_cents(i64::MIN);
+
+    /// Construct from a dollar amount, rounded to the nearest cent
+    ///
+    /// This never panics, see [`Usd::new_rounded`] for what happens to NaN and infinities. Use
+    /// [`Usd::try_new`] to reject them instead.
     pub fn new(amount: f64) -> Self {
-        Self { amount }
+        Self::new_rounded(amount, RoundingPolicy::default())
+    }
+
+    /// Construct from a dollar amount, or `None` if it's NaN or infinite
+    pub fn try_new(amount: f64) -> Option<Self> {
+        if amount.is_finite() {
+            Some(Self::new(amount))
+        } else {
+            None
+        }
+    }
+
+    /// Construct from a dollar amount, rounded to a whole cent with `policy`
+    ///
+    /// Amounts too big for an `i64` of cents, past about $92 quadrillion either way, saturate to
+    /// [`Usd::MAX`] or [`Usd::MIN`] instead of wrapping. Infinities saturate the same way, and
+    /// NaN is $0.00.
+    pub fn new_rounded(amount: f64, policy: RoundingPolicy) -> Self {
+        if amount.is_nan() {
+            return Self::ZERO;
+        }
+        // Float to int casts saturate, which is what we want for anything too big
+        Self::from_cents(policy.round_cents(amount))
+    }
+
+    /// Construct from a whole number of cents
+    ///
+    /// This is exact, so it's the way to load amounts you've stored as cents yourself. Every
+    /// `i64` is a valid amount.
+    pub const fn from_cents(cents: i64) -> Self {
+        Self { cents }
+    }
+
+    /// The amount as a whole number of cents
+    pub const fn cents(&self) -> i64 {
+        self.cents
     }
 
     /// Convert into a number for arithmetic
     ///
     /// This amount is in USD (surprise!)
     pub fn usd(&self) -> f64 {
-        self.amount
+        self.cents as f64 / 100.
+    }
+
+    /// Display with `locale`'s thousands and decimal separators, like `$22.663,40` for German
+    ///
+    /// It's still US dollars, so the symbol is always `$`. Signs, the alternate flag, and widths
+    /// work the same as for [`Display`](fmt::Display).
+    ///
+    /// ```rust
+    /// use relay_st_jude::{Locale, Usd};
+    ///
+    /// let raised = Usd::new(22_663.40);
+    /// assert_eq!(raised.display_with_locale(Locale::de).to_string(), "$22.663,40");
+    /// assert_eq!(format!("{:>12}", raised.display_with_locale(Locale::en)), "  $22,663.40");
+    /// ```
+    pub fn display_with_locale(&self, locale: Locale) -> LocalizedUsd {
+        LocalizedUsd { usd: *self, locale }
+    }
+
+    /// A short form for dashboards and social posts, like `$22.7K` or `$1.2M`
+    ///
+    /// Amounts of $1,000 and up are rounded half-up to one decimal of a thousand, million, or
+    /// billion, and a trailing `.0` is dropped, so $55,000 is `$55K`. Smaller amounts are shown
+    /// in full, like `$999.99`.
+    pub fn compact(&self) -> String {
+        // Each suffix, and how many cents it stands for
+        const SUFFIXES: &[(&str, u128)] = &[
+            ("K", 1_000 * 100),
+            ("M", 1_000_000 * 100),
+            ("B", 1_000_000_000 * 100),
+        ];
+
+        let cents = u128::from(self.cents.unsigned_abs());
+        if cents < SUFFIXES[0].1 {
+            return self.to_string();
+        }
+        let sign = if self.cents < 0 { "-" } else { "" };
+
+        // Tenths of the smallest unit that keeps it under 1,000, rounding half-up
+        let (suffix, tenths) = SUFFIXES
+            .iter()
+            .map(|&(suffix, unit)| (suffix, (cents * 10 + unit / 2) / unit))
+            .find(|&(suffix, tenths)| tenths < 10_000 || suffix == "B")
+            .expect("the last suffix is always used");
+
+        let whole = (tenths / 10).to_formatted_string(&Locale::en);
+        match tenths % 10 {
+            0 => format!("{}${}{}", sign, whole, suffix),
+            tenth => format!("{}${}.{}{}", sign, whole, tenth, suffix),
+        }
+    }
+
+    /// Subtract, stopping at $0 instead of going negative
+    ///
+    /// Plain `-` keeps negative results, which is usually what you want for "how far past the
+    /// milestone are we". This is for "how much is left to go".
+    pub fn saturating_sub(self, other: Usd) -> Self {
+        Self::from_cents(self.cents.saturating_sub(other.cents).max(0))
+    }
+
+    /// Add, or `None` if the total doesn't fit in an `i64` of cents
+    pub fn checked_add(self, other: Usd) -> Option<Self> {
+        self.cents.checked_add(other.cents).map(Self::from_cents)
+    }
+
+    /// Subtract, or `None` if the difference doesn't fit in an `i64` of cents
+    ///
+    /// Going below zero is fine, like with `-`. Use [`Usd::saturating_sub`] to stop at $0.
+    pub fn checked_sub(self, other: Usd) -> Option<Self> {
+        self.cents.checked_sub(other.cents).map(Self::from_cents)
+    }
+}
+
+/// Saturates at [`Usd::MAX`] and [`Usd::MIN`] instead of overflowing, see [`Usd::checked_add`]
+/// to tell when that happens
+impl Add for Usd {
+    type Output = Usd;
+
+    fn add(self, other: Usd) -> Usd {
+        Usd::from_cents(self.cents.saturating_add(other.cents))
+    }
+}
+
+/// Can go negative, see [`Usd::saturating_sub`] for an alternative
+///
+/// Saturates at [`Usd::MAX`] and [`Usd::MIN`] instead of overflowing, see [`Usd::checked_sub`]
+/// to tell when that happens.
+impl Sub for Usd {
+    type Output = Usd;
+
+    fn sub(self, other: Usd) -> Usd {
+        Usd::from_cents(self.cents.saturating_sub(other.cents))
+    }
+}
+
+impl AddAssign for Usd {
+    fn add_assign(&mut self, other: Usd) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Usd {
+    fn sub_assign(&mut self, other: Usd) {
+        *self = *self - other;
+    }
+}
+
+/// Adds up exactly, in cents
+///
+/// Like `+`, a total past [`Usd::MAX`] or [`Usd::MIN`] saturates there instead of overflowing.
+/// Use [`Usd::checked_add`] in a `try_fold` to find out when that happens.
+impl Sum for Usd {
+    fn sum<I: Iterator<Item = Usd>>(amounts: I) -> Usd {
+        amounts.fold(Usd::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Usd> for Usd {
+    fn sum<I: Iterator<Item = &'a Usd>>(amounts: I) -> Usd {
+        amounts.copied().sum()
+    }
+}
+
+/// Rounds to the nearest cent
+impl Mul<f64> for Usd {
+    type Output = Usd;
+
+    fn mul(self, factor: f64) -> Usd {
+        Usd::new(self.usd() * factor)
+    }
+}
+
+/// Rounds to the nearest cent
+impl Div<f64> for Usd {
+    type Output = Usd;
+
+    fn div(self, divisor: f64) -> Usd {
+        Usd::new(self.usd() / divisor)
+    }
+}
+
+/// How to round fractions of a cent
+///
+/// Everywhere this crate turns a dollar amount into whole cents, it goes through one of these.
+/// Unless you pick otherwise, that's [`RoundingPolicy::HalfEven`] (banker's rounding), which
+/// doesn't bias totals upwards the way [`RoundingPolicy::HalfUp`] does.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum RoundingPolicy {
+    /// Round halves away from zero: $0.005 becomes $0.01, and -$0.005 becomes -$0.01
+    HalfUp,
+
+    /// Round halves to the nearest even cent: $0.005 becomes $0.00, $0.015 becomes $0.02
+    #[default]
+    HalfEven,
+
+    /// Round towards negative infinity
+    Floor,
+
+    /// Round towards positive infinity
+    Ceiling,
+}
+
+impl RoundingPolicy {
+    /// Round a dollar amount to whole cents
+    ///
+    /// Dollar amounts like `0.005` can't be represented exactly by an `f64`, so values within a
+    /// millionth of a cent of a whole or half cent are treated as landing on it exactly. Without
+    /// that, `22_663.40` would floor to 2266339 cents.
+    pub fn round_cents(self, amount: f64) -> i64 {
+        let cents = (amount * 100. * 1e6).round() / 1e6;
+
+        let rounded = match self {
+            RoundingPolicy::HalfUp => cents.round(),
+            RoundingPolicy::HalfEven => cents.round_ties_even(),
+            RoundingPolicy::Floor => cents.floor(),
+            RoundingPolicy::Ceiling => cents.ceil(),
+        };
+
+        rounded as i64
     }
 }
 
 /// The GraphQL API has a complex, fully generic Currency type.
 /// I don't care, I just want USD. 🎇🇺🇸🦅🎆
 ///
-/// I'm sure I'll regret this later.
-fn deserialize_f64_from_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+/// I'm sure I'll regret this later. (For campaigns that aren't in USD, see [`RawCampaign`].)
+fn deserialize_cents_from_str<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    StrCents::deserialize(deserializer).map(|v| v.0)
+}
+
+/// Parse an amount that may be `{"currency": "USD", "value": null}`
+fn deserialize_nullable_amount<'de, D>(deserializer: D) -> Result<Option<Usd>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct NullableUsd {
+        #[serde(default)]
+        currency: Option<Currency>,
+
+        #[serde(default, deserialize_with = "deserialize_optional_cents_from_str")]
+        value: Option<i64>,
+    }
+
+    let amount = match Option::<NullableUsd>::deserialize(deserializer)? {
+        Some(amount) => amount,
+        None => return Ok(None),
+    };
+    UnsupportedCurrency::check(amount.currency).map_err(de::Error::custom)?;
+    Ok(amount.value.map(Usd::from_cents))
+}
+
+fn deserialize_optional_cents_from_str<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    String::deserialize(deserializer)?
-        .parse()
-        .map_err(de::Error::custom)
+    Ok(Option::<StrCents>::deserialize(deserializer)?.map(|v| v.0))
+}
+
+/// A dollar amount sent as a JSON string, like `"22663.40"`, or a number, in cents
+///
+/// This parses straight from the input, rather than copying the string into a `String` first.
+struct StrCents(i64);
+
+impl<'de> Deserialize<'de> for StrCents {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = StrCents;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a number, or a number in a string")
+            }
+
+            // Tiltify sends strings, but cached copies of responses may have plain numbers
+            fn visit_u64<E: de::Error>(self, n: u64) -> Result<StrCents, E> {
+                i64::try_from(n)
+                    .ok()
+                    .and_then(|n| n.checked_mul(100))
+                    .map(StrCents)
+                    .ok_or_else(|| de::Error::custom(format!("amount {} is too large", n)))
+            }
+
+            fn visit_i64<E: de::Error>(self, n: i64) -> Result<StrCents, E> {
+                n.checked_mul(100)
+                    .map(StrCents)
+                    .ok_or_else(|| de::Error::custom(format!("amount {} is too large", n)))
+            }
+
+            fn visit_f64<E: de::Error>(self, amount: f64) -> Result<StrCents, E> {
+                if amount.is_finite() {
+                    Ok(StrCents(Usd::new(amount).cents()))
+                } else {
+                    Err(de::Error::custom(format!(
+                        "amount {} isn't a finite number",
+                        amount
+                    )))
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<StrCents, E> {
+                if let Some(cents) = parse_decimal_cents(s) {
+                    return Ok(StrCents(cents));
+                }
+
+                // Anything fancier, like an exponent, goes through a float
+                match s.parse::<f64>() {
+                    Ok(amount) if amount.is_finite() => Ok(StrCents(Usd::new(amount).cents())),
+                    Ok(_) => Err(de::Error::custom(format!(
+                        "amount {:?} isn't a finite number",
+                        s
+                    ))),
+                    Err(e) => Err(de::Error::custom(e)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Written the way the API sends amounts, like `{"currency": "USD", "value": "22663.40"}`
+impl Serialize for Usd {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        AmountJson::new(Some(*self)).serialize(serializer)
+    }
+}
+
+/// Write an amount that may be missing as `{"currency": "USD", "value": null}`
+fn serialize_nullable_amount<S>(amount: &Option<Usd>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    AmountJson::new(*amount).serialize(serializer)
+}
+
+#[derive(Serialize)]
+struct AmountJson {
+    currency: &'static str,
+    value: Option<String>,
+}
+
+impl AmountJson {
+    fn new(amount: Option<Usd>) -> Self {
+        Self {
+            currency: "USD",
+            value: amount.map(|amount| decimal_string(amount.cents)),
+        }
+    }
+}
+
+/// Cents as a plain decimal with exactly two places, like `-22663.40`
+fn decimal_string(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!(
+        "{}{}.{:02}",
+        sign,
+        cents.unsigned_abs() / 100,
+        cents.unsigned_abs() % 100
+    )
+}
+
+/// Parse a plain decimal like `-22663.405` into cents exactly
+///
+/// Fractions of a cent round half to even, the same as [`RoundingPolicy::default`].
+/// Returns `None` for anything else, or amounts too big for an `i64` of cents.
+fn parse_decimal_cents(s: &str) -> Option<i64> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !all_digits(whole) || !all_digits(fraction) {
+        return None;
+    }
+
+    // Split the fraction into whole cents and the rest, which only matters for rounding
+    let digit = |i: usize| {
+        fraction
+            .as_bytes()
+            .get(i)
+            .map_or(0, |b| i64::from(b - b'0'))
+    };
+    let mut cents = whole
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(100)?
+        .checked_add(digit(0) * 10 + digit(1))?;
+
+    let rest = fraction.get(2..).unwrap_or("");
+    let first_rest = rest.bytes().next().map_or(0, |b| b - b'0');
+    let past_half = rest.bytes().skip(1).any(|b| b != b'0');
+    if first_rest > 5 || (first_rest == 5 && (past_half || cents % 2 == 1)) {
+        cents = cents.checked_add(1)?;
+    }
+
+    Some(if negative { -cents } else { cents })
 }
 
 /// Display the Usd amount in a typical currency fashion
+///
+/// Negative amounts display as `-$123.45`, or accounting style as `($123.45)` with the alternate
+/// flag, `{:#}`.
+///
+/// The rest of the format spec works too. A width pads the whole thing, sign included, on the
+/// left unless `<` or `^` says otherwise, with any fill character. A precision sets the number of
+/// decimals, rounding half to even, so `{:.0}` is `$22,663`.
+///
+/// This always uses English separators, like `$22,663.40`. See [`Usd::display_with_locale`] for
+/// others.
 impl fmt::Display for Usd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display_with_locale(Locale::en), f)
+    }
+}
+
+/// A [`Usd`] displayed with a locale's separators, from [`Usd::display_with_locale`]
+#[derive(Copy, Clone, Debug)]
+pub struct LocalizedUsd {
+    usd: Usd,
+    locale: Locale,
+}
+
+impl fmt::Display for LocalizedUsd {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // I can't figure out how to format with commas and a fixed amount of decimals...
-        // So we'll format two ints instead
-        let dollars: u64 = self.amount as u64;
-        let cents: u8 = (100. * self.amount.fract() + 0.005) as u8;
+        // So we'll format two ints instead.
+        let decimals = f.precision().unwrap_or(2);
+        let total_cents = self.usd.cents.unsigned_abs();
+        let (dollars, fraction) = if decimals >= 2 {
+            let cents = format!("{:02}{}", total_cents % 100, "0".repeat(decimals - 2));
+            (total_cents / 100, cents)
+        } else {
+            // Round half to even into tenths, or whole dollars
+            let unit = 10u64.pow(2 - decimals as u32);
+            let (mut units, rest) = (total_cents / unit, total_cents % unit);
+            if rest * 2 > unit || (rest * 2 == unit && units % 2 == 1) {
+                units += 1;
+            }
 
-        // This is our main dollar amount as a string, hurray!
-        // We'll use this string and apply width to it directly.
-        let s = format!("${}.{:02}", dollars.to_formatted_string(&Locale::en), cents);
+            let scale = 10u64.pow(decimals as u32);
+            let fraction = match decimals {
+                0 => String::new(),
+                _ => format!("{}", units % scale),
+            };
+            (units / scale, fraction)
+        };
+        let negative = self.usd.cents < 0 && (dollars > 0 || fraction.bytes().any(|b| b != b'0'));
 
-        if let Some(width) = f.width() {
-            write!(f, "{:>width$}", s, width = width,)
+        // This is our main dollar amount as a string, hurray!
+        let dollars = dollars.to_formatted_string(&self.locale);
+        let s = if fraction.is_empty() {
+            format!("${}", dollars)
         } else {
-            // No width requested? Write it direct
-            write!(f, "{}", s)
+            format!("${}{}{}", dollars, self.locale.decimal(), fraction)
+        };
+        let s = match (negative, f.alternate()) {
+            (false, _) => s,
+            (true, false) => format!("-{}", s),
+            (true, true) => format!("({})", s),
+        };
+
+        text::pad_formatter(f, &s, text::Align::Right)
+    }
+}
+
+/// Parse a dollar amount the way [`Usd`]'s `Display` writes it, like `$1,234.56`
+///
+/// The `$`, the commas, and the cents are all optional, so `1234.56`, `1,234`, and `$5` work
+/// too. Commas have to separate groups of three digits, and there can be at most two digits of
+/// cents, so nothing is silently rounded. A leading `-` makes the amount negative.
+///
+/// Displaying and then parsing gives back the same amount.
+///
+/// ```rust
+/// use relay_st_jude::Usd;
+///
+/// let amount: Usd = "$1,234.56".parse().unwrap();
+/// assert_eq!(amount.cents(), 123_456);
+/// assert_eq!(amount.to_string().parse::<Usd>().unwrap(), amount);
+///
+/// assert!("$1.2.3".parse::<Usd>().is_err());
+/// ```
+impl FromStr for Usd {
+    type Err = ParseUsdError;
+
+    fn from_str(s: &str) -> Result<Self, ParseUsdError> {
+        let s = s.trim();
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let s = s.strip_prefix('$').unwrap_or(s);
+
+        let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if fraction.contains('.') {
+            return Err(ParseUsdError::MultipleDecimalPoints);
+        }
+        if let Some(c) = whole
+            .chars()
+            .chain(fraction.chars())
+            .find(|&c| !c.is_ascii_digit() && c != ',')
+        {
+            return Err(ParseUsdError::UnexpectedCharacter(c));
+        }
+        if whole.is_empty() {
+            return Err(ParseUsdError::NoDigits);
+        }
+        if fraction.contains(',') {
+            return Err(ParseUsdError::MisplacedComma);
+        }
+        if fraction.len() > 2 {
+            return Err(ParseUsdError::TooManyDecimals);
+        }
+
+        // Either no commas at all, or 1-3 leading digits and then groups of exactly three
+        let mut groups = whole.split(',');
+        let first = groups.next().unwrap_or("");
+        if whole.contains(',')
+            && (first.is_empty() || first.len() > 3 || groups.any(|group| group.len() != 3))
+        {
+            return Err(ParseUsdError::MisplacedComma);
+        }
+
+        let digits: String = whole.chars().filter(|&c| c != ',').collect();
+        let cents = parse_decimal_cents(&format!("{}.{}", digits, fraction))
+            .ok_or(ParseUsdError::TooLarge)?;
+        Ok(Usd::from_cents(if negative { -cents } else { cents }))
+    }
+}
+
+/// Why a string couldn't be parsed as a [`Usd`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseUsdError {
+    /// There's no amount, just (at most) a sign, a `$`, or cents
+    NoDigits,
+
+    /// Something other than digits, commas, and one decimal point
+    UnexpectedCharacter(char),
+
+    /// More than one `.`, like `$1.2.3`
+    MultipleDecimalPoints,
+
+    /// A comma that isn't separating thousands, like `12,34.56` or `$1.23,4`
+    MisplacedComma,
+
+    /// More than two digits after the decimal point
+    TooManyDecimals,
+
+    /// Too many cents to fit in an `i64`
+    TooLarge,
+}
+
+impl fmt::Display for ParseUsdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid dollar amount: ")?;
+        match self {
+            ParseUsdError::NoDigits => f.write_str("expected digits, like $1,234.56"),
+            ParseUsdError::UnexpectedCharacter(c) => write!(f, "unexpected character {:?}", c),
+            ParseUsdError::MultipleDecimalPoints => f.write_str("more than one decimal point"),
+            ParseUsdError::MisplacedComma => {
+                f.write_str("commas can only separate groups of three digits")
+            }
+            ParseUsdError::TooManyDecimals => {
+                f.write_str("more than two digits after the decimal point")
+            }
+            ParseUsdError::TooLarge => f.write_str("too large"),
         }
     }
 }
 
+impl std::error::Error for ParseUsdError {}
+
 // TODO: This should probably be an enum - both fields here are mutually exclusive
 #[derive(Deserialize, Clone, Debug, PartialEq)]
-struct ApiResponse {
-    data: Option<ApiData>,
+struct ApiResponse<C = Campaign> {
+    data: Option<ApiData<C>>,
 
     #[serde(default)]
     errors: Vec<ApiError>,
 }
 
+impl<C> ApiResponse<C> {
+    /// The campaign, or the query's errors
+    fn into_campaign(self) -> Result<C, Report> {
+        if let Some(data) = self.data {
+            Ok(data.campaign)
+        } else {
+            let errors: Vec<String> = self.errors.iter().map(|e| format!("{}", e)).collect();
+            let errors = errors.join("\n");
+
+            let report = Report::msg(format!("Campaign Query failed:\n{}", errors));
+            Err(report)
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq)]
-struct ApiData {
-    campaign: Campaign,
+struct ApiData<C> {
+    campaign: C,
 }
 
 #[derive(Deserialize, Clone, Debug, PartialEq)]
@@ -140,100 +866,541 @@ impl fmt::Display for ApiError {
     }
 }
 
-fn build_graph_ql_query(vanity: &str, slug: &str) -> serde_json::Value {
-    json!({
-        "operationName": "get_campaign_by_vanity_and_slug",
-        "variables": {
-            "vanity": vanity,
-            "slug": slug,
-        },
-        "query": indoc::indoc!(r#"query get_campaign_by_vanity_and_slug($vanity: String, $slug: String) {
-            campaign(vanity: $vanity, slug: $slug) {
-                name
-                description
-                totalAmountRaised {
-                    currency
-                    value
-                }
-                goal {
-                    currency
-                    value
-                }
-                milestones {
-                    name
-                    amount {
-                        currency
-                        value
-                    }
-                }
-            }
-        }"#)
-    })
+/// An opaque, stable identifier for a campaign
+///
+/// Vanities and slugs can be renamed mid-campaign, but the id stays put. Tiltify doesn't promise
+/// anything about the format, so this is kept as a string even though it currently looks numeric.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CampaignId(String);
+
+impl CampaignId {
+    /// Construct from an id string, like one previously read from [`Campaign::id`]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The id as it's sent to the API
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
-/// A fund raising campaign for a good cause
-#[derive(Deserialize, Clone, Debug, PartialEq)]
+impl fmt::Display for CampaignId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Always written as a string, which reads back the same
+impl Serialize for CampaignId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// The API currently sends ids as JSON numbers, but we accept strings too in case that changes.
+impl<'de> Deserialize<'de> for CampaignId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_id(deserializer).map(Self)
+    }
+}
+
+/// An opaque, stable identifier for a milestone
+///
+/// Unlike its description, this stays the same when organizers edit a milestone. Like
+/// [`CampaignId`], it's kept as a string.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MilestoneId(String);
+
+impl MilestoneId {
+    /// Construct from an id string, like one previously read from [`Milestone::id`]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The id as the API sent it
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for MilestoneId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Always written as a string, which reads back the same
+impl Serialize for MilestoneId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// Numbers or strings, the same as [`CampaignId`]
+impl<'de> Deserialize<'de> for MilestoneId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_id(deserializer).map(Self)
+    }
+}
+
+fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawId {
+        Str(String),
+        Int(u64),
+    }
+
+    Ok(match RawId::deserialize(deserializer)? {
+        RawId::Str(s) => s,
+        RawId::Int(n) => n.to_string(),
+    })
+}
+
+/// Where a campaign is in its lifecycle
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CampaignStatus {
+    /// The campaign exists but hasn't launched yet. Expect $0 raised.
+    Unpublished,
+
+    /// The campaign is live and taking donations
+    Published,
+
+    /// The campaign is over
+    Retired,
+
+    /// A status this crate doesn't know about yet
+    #[serde(other)]
+    Unknown,
+}
+
+/// The team running a campaign
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Team {
+    /// The team's display name, like "Relay FM"
+    pub name: String,
+
+    /// The team's picture, if it has one
+    #[serde(default)]
+    pub avatar: Option<Avatar>,
+}
+
+/// The charity a campaign raises money for, like St. Jude Children's Research Hospital
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Cause {
+    /// The cause's display name
+    pub name: String,
+
+    /// The cause's slug on Tiltify, like `st-jude-children-s-research-hospital`
+    pub slug: String,
+}
+
+/// A live stream attached to a campaign, like the Podcastathon on Twitch
+///
+/// See [`Campaign::livestream_url`] for somewhere to watch it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Livestream {
+    /// Where it's streaming, like "twitch"
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The channel on that service, like "relayfm"
+    pub channel: String,
+}
+
+/// A fund raising campaign for a good cause
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Campaign {
+    /// Stable id of the campaign
+    ///
+    /// Prefer this over the vanity & slug when keying your own storage. It's optional so that
+    /// responses saved before we asked for it still parse.
+    #[serde(default)]
+    pub id: Option<CampaignId>,
+
     /// Registered name of the campaign
     ///
     /// Useful for uniquely identifying a campaign for humans
     pub name: String,
 
+    /// The campaign's slug, the last part of its URL like `relay-st-jude-21`
+    ///
+    /// This is `None` for responses saved before we asked for it.
+    #[serde(default)]
+    pub slug: Option<String>,
+
     /// A description of what this campaign is for and about
+    ///
+    /// Empty if it was fetched with a [`CampaignQuery`] that leaves it out.
+    #[serde(default)]
     pub description: String,
 
+    /// Whether the campaign has launched, if the API told us
+    ///
+    /// Unpublished campaigns are still returned from [`fetch_by`](Campaign::fetch_by) rather than
+    /// treated as an error, so check this before showing the amount raised.
+    #[serde(default)]
+    pub status: Option<CampaignStatus>,
+
+    /// The campaign's picture, if it has one
+    ///
+    /// This is `None` when fetched with [`CampaignQuery::Amounts`].
+    #[serde(default)]
+    pub avatar: Option<Avatar>,
+
+    /// The team running the campaign
+    ///
+    /// This is `None` for campaigns run by one person, and when fetched with
+    /// [`CampaignQuery::Amounts`].
+    #[serde(default)]
+    pub team: Option<Team>,
+
+    /// The live stream attached to the campaign
+    ///
+    /// Most of the year there isn't one. It's only asked for by [`CampaignQuery::Details`], so
+    /// it's `None` when fetched any other way.
+    #[serde(default)]
+    pub livestream: Option<Livestream>,
+
+    /// The charity the campaign is raising money for
+    ///
+    /// It's only asked for by [`CampaignQuery::Details`], so it's `None` when fetched any other
+    /// way.
+    #[serde(default)]
+    pub cause: Option<Cause>,
+
     /// The current amount of money raised
     #[serde(rename = "totalAmountRaised")]
     pub total_amount_raised: Usd,
 
     /// The goal for money raised
+    ///
+    /// Organizers often raise this during a campaign, see
+    /// [`original_goal`](Campaign::original_goal).
     pub goal: Usd,
 
+    /// The goal the campaign started out with, if it's known
+    ///
+    /// This is `None` when the API sends `null`, which it does for campaigns that never changed
+    /// their goal, and for responses saved before we asked for it.
+    #[serde(
+        rename = "originalGoal",
+        default,
+        deserialize_with = "deserialize_nullable_amount",
+        serialize_with = "serialize_nullable_amount"
+    )]
+    pub original_goal: Option<Usd>,
+
+    /// When the campaign was published and started taking donations
+    ///
+    /// Tiltify sends ISO 8601 timestamps, but milliseconds since 1970 parse too. This is only
+    /// asked for by [`CampaignQuery::Details`], so it's `None` when fetched any other way.
+    #[serde(
+        rename = "publishedAt",
+        default,
+        deserialize_with = "timestamp::deserialize_optional",
+        serialize_with = "timestamp::serialize_optional"
+    )]
+    pub published_at: Option<SystemTime>,
+
+    /// When the campaign stops taking donations, if it's scheduled to
+    ///
+    /// See [`Campaign::has_ended`] and [`Campaign::time_remaining`]. This is only asked for by
+    /// [`CampaignQuery::Details`].
+    #[serde(
+        rename = "endsAt",
+        default,
+        deserialize_with = "timestamp::deserialize_optional",
+        serialize_with = "timestamp::serialize_optional"
+    )]
+    pub ends_at: Option<SystemTime>,
+
     /// A list of milestones set for the campaign currently, including their progress
+    ///
+    /// These are in the order the API sent them, which isn't sorted. Use
+    /// [`Campaign::sorted_milestones`] for cheapest first.
     #[serde(default)]
     pub milestones: Vec<Milestone>,
+
+    /// What donors can get for giving enough, in the order the API sent them
+    ///
+    /// They're only asked for by [`CampaignQuery::Details`], so this is empty when fetched any
+    /// other way. See [`Campaign::available_rewards`] for the ones that can still be claimed.
+    #[serde(default)]
+    pub rewards: Vec<Reward>,
+
+    /// The vanity and slug this was fetched with, if it was fetched by them
+    ///
+    /// The API doesn't send the vanity back, only the [`slug`](Campaign::slug), so this is
+    /// `None` for parsed responses and [`fetch_by_id`](Campaign::fetch_by_id). Use it to link to
+    /// the campaign with [`Campaign::url`].
+    #[serde(skip)]
+    pub page: Option<CampaignPage>,
 }
 
 impl Campaign {
-    /// Fetch the Relay St Jude campaign status from online
+    /// Whether the campaign is taking donations
+    ///
+    /// That's a [`status`](Campaign::status) of [`Published`](CampaignStatus::Published). A
+    /// campaign whose status wasn't fetched isn't known to be live, so this is `false` for it.
+    pub fn is_live(&self) -> bool {
+        self.status == Some(CampaignStatus::Published)
+    }
+
+    /// Fetch the latest Relay FM for St. Jude campaign from online
+    ///
+    /// That's [`KnownCampaign::latest`](relay::KnownCampaign::latest). For other known years, use
+    /// [`fetch_known`](Campaign::fetch_known), and for any other campaign,
+    /// [`fetch_by`](Campaign::fetch_by).
     pub fn fetch() -> Result<Self, Report> {
-        Self::fetch_by("@relay-fm", "relay-st-jude-21")
+        Self::fetch_known(relay::KnownCampaign::latest())
+    }
+
+    /// Fetch one year's Relay FM for St. Jude campaign from online
+    pub fn fetch_known(known: relay::KnownCampaign) -> Result<Self, Report> {
+        Self::fetch_by(known.vanity(), known.slug())
+    }
+
+    /// Fetch every year's Relay FM for St. Jude campaign from online
+    ///
+    /// A year that fails to fetch, like an old campaign that's been taken down, ends up in
+    /// [`errors`](relay::AllKnownCampaigns::errors) while the rest are still returned.
+    pub fn fetch_all_known() -> relay::AllKnownCampaigns {
+        relay::fetch_all_with(relay::KnownCampaign::ALL, Self::fetch_known)
     }
 
     /// Fetch an arbitrary vanity & slug from online
     pub fn fetch_by(vanity: &str, slug: &str) -> Result<Self, Report> {
-        // TODO: Don't hard code these, maybe take them from Clap or something.
         let json = Self::fetch_json(vanity, slug)?;
+        Ok(Self::from_json(&json)?.fetched_from(vanity, slug))
+    }
 
-        let res: ApiResponse = serde_json::from_str(&json)?;
-        if let Some(data) = res.data {
-            Ok(data.campaign)
-        } else {
-            let errors: Vec<String> = res.errors.iter().map(|e| format!("{}", e)).collect();
-            let errors = errors.join("\n");
+    /// Fetch a vanity & slug from online, asking only for the fields in `query`
+    ///
+    /// Use [`CampaignQuery::for_output`] to pick the smallest query for what you're showing.
+    /// Fields that weren't asked for are left empty.
+    pub fn fetch_by_selecting(
+        vanity: &str,
+        slug: &str,
+        query: CampaignQuery,
+    ) -> Result<Self, Report> {
+        let op = ops::CampaignBySlug { vanity, slug };
+        let campaign = match query {
+            CampaignQuery::Full => run(&op),
+            CampaignQuery::NoDescription => run(&ops::CampaignSummaryBySlug(op)),
+            CampaignQuery::Amounts => run(&ops::CampaignAmountsBySlug(op)),
+            CampaignQuery::Details => run(&ops::CampaignDetailsBySlug(op)),
+        }?;
+        Ok(campaign.fetched_from(vanity, slug))
+    }
 
-            let report = Report::msg(format!("Campaign Query failed:\n{}", errors));
-            Err(report)
-        }
+    /// Fetch a campaign by its [`CampaignId`] from online
+    ///
+    /// Unlike [`fetch_by`](Campaign::fetch_by), this keeps working when the campaign's slug is
+    /// renamed.
+    pub fn fetch_by_id(id: &CampaignId) -> Result<Self, Report> {
+        run(&ops::CampaignById { id })
     }
 
     /// Fetch just the json for a vanity & slug from online
     ///
-    /// This can be parsed into a [`Campaign`](Campaign) object, but prefer calling
+    /// This can be parsed into a [`Campaign`](Campaign) object with
+    /// [`from_json`](Campaign::from_json), like after saving it to disk, but prefer calling
     /// [`fetch`](Campaign::fetch) directly or [`fetch_by`](Campaign::fetch_by).
     ///
     /// Use this if you're getting deserialization errors.
+    ///
+    /// All of the fetch methods talk to `https://api.tiltify.com`, unless the `TILTIFY_API_URL`
+    /// environment variable is set to another endpoint.
     pub fn fetch_json(vanity: &str, slug: &str) -> Result<String, Report> {
-        const API_URL: &str = "https://api.tiltify.com";
+        let op = ops::CampaignBySlug { vanity, slug };
+        post_query(
+            ops::CampaignBySlug::name(),
+            &RequestBody::for_operation(&op),
+        )
+    }
 
-        let json = ureq::post(API_URL)
-            .send_json(build_graph_ql_query(vanity, slug))?
-            .into_string()?;
+    /// Fetch the Relay FM for St. Jude campaign, failing if the response has any fields we don't
+    /// know
+    ///
+    /// This is a thin alias for [`relay::fetch_current_strict_schema`].
+    pub fn fetch_strict_schema() -> Result<Self, Report> {
+        relay::fetch_current_strict_schema()
+    }
 
-        Ok(json)
+    /// Fetch a vanity & slug, failing if the response has any fields we don't know
+    ///
+    /// See [`from_json_strict_schema`](Campaign::from_json_strict_schema) for details.
+    pub fn fetch_by_strict_schema(vanity: &str, slug: &str) -> Result<Self, Report> {
+        let json = Self::fetch_json(vanity, slug)?;
+        Ok(Self::from_json_strict_schema(&json)?.fetched_from(vanity, slug))
+    }
+
+    fn fetched_from(self, vanity: &str, slug: &str) -> Self {
+        Self {
+            page: Some(CampaignPage::new(vanity, slug)),
+            ..self
+        }
+    }
+
+    /// Parse an API response, failing if it has any fields we don't know about
+    ///
+    /// Normal parsing ignores unknown fields so that Tiltify can add things without breaking us.
+    /// This is the opposite: any field, at any level, that this crate doesn't model is an error
+    /// naming it. This is meant for canary jobs that want to notice schema drift early, not for
+    /// everyday use.
+    ///
+    /// Milestones without an amount are an error here too, rather than just a warning.
+    pub fn from_json_strict_schema(json: &str) -> Result<Self, Report> {
+        serde_json::from_str::<strict::ApiResponse>(json)
+            .map_err(|e| Report::msg(format!("Campaign schema drift: {}", e)))?;
+
+        let campaign = Self::from_json(json)?;
+        if let Some(m) = campaign.milestones.iter().find(|m| m.amount.is_none()) {
+            return Err(Report::msg(format!(
+                "Milestone {:?} has no amount",
+                m.description
+            )));
+        }
+
+        Ok(campaign)
+    }
+
+    /// Parse a raw API response, like one saved from [`fetch_json`](Campaign::fetch_json)
+    ///
+    /// This takes the whole GraphQL response body, and handles it exactly like
+    /// [`fetch_by`](Campaign::fetch_by) does, including failing with the API's errors if it
+    /// sent any. So a saved response can be replayed without the network:
+    ///
+    /// ```rust
+    /// use relay_st_jude::Campaign;
+    ///
+    /// # fn main() -> Result<(), relay_st_jude::Report> {
+    /// let json = std::fs::read_to_string("src/example-response.json")?;
+    /// let campaign = Campaign::from_json(&json)?;
+    /// assert_eq!(campaign.name, "Relay FM for St. Jude 2021");
+    ///
+    /// let failed = r#"{"data": null, "errors": [{"message": "campaign not found"}]}"#;
+    /// assert!(Campaign::from_json(failed).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, Report> {
+        let res = serde_json::from_str(json)
+            .map_err(|e| Self::deserialize_error(e, || serde_json::from_str(json).ok()))?;
+        Self::from_api_response(res)
+    }
+
+    /// Parse a raw API response, from the bytes it arrived as
+    ///
+    /// This is the same as parsing [`fetch_json`](Campaign::fetch_json)'s output, but skips
+    /// checking and copying the bytes into a `String` first.
+    pub fn from_slice(json: &[u8]) -> Result<Self, Report> {
+        let res = serde_json::from_slice(json)
+            .map_err(|e| Self::deserialize_error(e, || serde_json::from_slice(json).ok()))?;
+        Self::from_api_response(res)
+    }
+
+    /// Parse a raw API response read from `reader`, like a saved response file
+    ///
+    /// See [`from_json`](Campaign::from_json).
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<Self, Report> {
+        let mut json = vec![];
+        reader.read_to_end(&mut json)?;
+        Self::from_slice(&json)
+    }
+
+    /// Report `e`, or [`UnsupportedCurrency`] if that's why deserializing failed
+    ///
+    /// Serde's errors are only strings, so this parses the response again to find the currency.
+    fn deserialize_error(
+        e: serde_json::Error,
+        raw: impl FnOnce() -> Option<ApiResponse<RawCampaign>>,
+    ) -> Report {
+        match raw().and_then(UnsupportedCurrency::in_response) {
+            Some(unsupported) => Report::new(unsupported),
+            None => e.into(),
+        }
+    }
+
+    fn from_api_response(res: ApiResponse) -> Result<Self, Report> {
+        let campaign = res.into_campaign()?;
+        for m in campaign.milestones.iter().filter(|m| m.amount.is_none()) {
+            tracing::warn!(milestone = %m.description, "milestone has no amount yet");
+        }
+        Ok(campaign)
+    }
+}
+
+/// Parse a raw API response that's already been parsed as JSON
+impl TryFrom<Value> for Campaign {
+    type Error = Report;
+
+    fn try_from(response: Value) -> Result<Self, Report> {
+        Self::try_from(&response)
     }
 }
 
+/// Like the owned version, but leaves `response` alone for you to keep using
+impl TryFrom<&Value> for Campaign {
+    type Error = Report;
+
+    fn try_from(response: &Value) -> Result<Self, Report> {
+        let res = ApiResponse::deserialize(response)
+            .map_err(|e| Self::deserialize_error(e, || ApiResponse::deserialize(response).ok()))?;
+        Self::from_api_response(res)
+    }
+}
+
+/// Where we send GraphQL queries, unless `TILTIFY_API_URL` says otherwise
+const API_URL: &str = "https://api.tiltify.com";
+
+/// Send `op` and parse its response
+fn run<O: Operation>(op: &O) -> Result<O::Output, Report> {
+    let json = post_query(O::name(), &RequestBody::for_operation(op))?;
+    O::parse(serde_json::from_str(&json)?)
+}
+
+fn post_query(operation: &str, body: &RequestBody) -> Result<String, Report> {
+    // Mostly useful for pointing at a local mock server in tests
+    let url = std::env::var("TILTIFY_API_URL").unwrap_or_else(|_| API_URL.to_string());
+
+    let body = body.as_bytes();
+    tracing::debug!(url = %url, operation, bytes = body.len(), "sending GraphQL request");
+
+    let start = std::time::Instant::now();
+    let json = ureq::post(&url)
+        .set("Content-Type", "application/json")
+        .send_bytes(body)
+        .map_err(|e| {
+            tracing::warn!(url = %url, "GraphQL request failed: {}", e);
+            e
+        })?
+        .into_string()?;
+
+    tracing::info!(url = %url, elapsed = ?start.elapsed(), "fetched campaign");
+    tracing::debug!(operation, bytes = json.len(), "received GraphQL response");
+
+    Ok(json)
+}
+
 #[cfg(test)]
 mod t {
     use super::*;
@@ -247,28 +1414,49 @@ mod t {
         let expected = ApiResponse {
             data: Some(ApiData {
                 campaign: Campaign {
+                    id: Some(CampaignId::new("121745")),
                     name: "Relay FM for St. Jude 2021".to_string(),
+                    slug: Some("relay-st-jude-21".to_string()),
                     description: DESCRIPTION.to_string(),
+                    status: Some(CampaignStatus::Published),
+                    avatar: Some(Avatar {
+                        src: "https://assets.tiltify.com/uploads/event/thumbnail/121745/blob-4eeff29f-7090-4aba-bb8c-098bb7887576.png".to_string(),
+                        alt: "alt".to_string(),
+                        width: Some(64),
+                        height: Some(64),
+                    }),
+                    team: None,
+                    livestream: None,
+                    cause: None,
 
                     goal: Usd::new(333_333.33),
+                    original_goal: Some(Usd::new(100.)),
+                    published_at: None,
+                    ends_at: None,
+                    rewards: vec![],
+                    page: None,
                     total_amount_raised: Usd::new(22_663.40),
 
                     milestones: vec![
                         Milestone {
-                            amount: Usd::new(75_000.00),
+                            id: Some(MilestoneId::new("128970")),
+                            amount: Some(Usd::new(75_000.00)),
                             description: "Stephen & Myke go to space via KSP".to_string(),
                         },
                         Milestone {
-                            amount: Usd::new(55_000.00),
+                            id: Some(MilestoneId::new("127774")),
+                            amount: Some(Usd::new(55_000.00)),
                             description: "Stephen dissembles his NeXTCube on stream".to_string(),
                         },
                         Milestone {
-                            amount: Usd::new(20_000.00),
+                            id: Some(MilestoneId::new("127775")),
+                            amount: Some(Usd::new(20_000.00)),
                             description: "Myke and Stephen attempt Flight Simulator again"
                                 .to_string(),
                         },
                         Milestone {
-                            amount: Usd::new(196_060.44),
+                            id: Some(MilestoneId::new("126992")),
+                            amount: Some(Usd::new(196_060.44)),
                             description: "$1 million raised in 3 years!".to_string(),
                         },
                     ],
@@ -281,6 +1469,871 @@ mod t {
         assert_eq!(expected, serde_json::from_str(RESPONSE).unwrap());
     }
 
+    /// Verify that a campaign that hasn't launched yet still parses, instead of erroring
+    #[test]
+    fn unpublished_response() {
+        const RESPONSE: &str = include_str!("example-response-unpublished.json");
+
+        let res: ApiResponse = serde_json::from_str(RESPONSE).unwrap();
+        let campaign = res.data.unwrap().campaign;
+
+        assert_eq!(campaign.status, Some(CampaignStatus::Unpublished));
+        assert_eq!(campaign.total_amount_raised, Usd::ZERO);
+        assert_eq!(campaign.milestones.len(), 2);
+    }
+
+    #[test]
+    fn streams() {
+        let campaign =
+            Campaign::from_json(include_str!("example-response-livestream.json")).unwrap();
+        assert_eq!(
+            campaign.livestream,
+            Some(Livestream {
+                kind: "twitch".to_string(),
+                channel: "relayfm".to_string(),
+            })
+        );
+
+        for json in [
+            include_str!("example-response-livestream.json"),
+            include_str!("example-response-livestream-youtube.json"),
+        ] {
+            Campaign::from_json_strict_schema(json).unwrap();
+        }
+
+        // Not streaming, or saved before we asked
+        for json in [
+            include_str!("example-response.json"),
+            include_str!("example-response-null-amount.json"),
+        ] {
+            assert_eq!(Campaign::from_json(json).unwrap().livestream, None);
+        }
+    }
+
+    #[test]
+    fn causes() {
+        let campaign = Campaign::from_json(include_str!("example-response-cause.json")).unwrap();
+        assert_eq!(
+            campaign.cause,
+            Some(Cause {
+                name: "St. Jude Children's Research Hospital".to_string(),
+                slug: "st-jude-children-s-research-hospital".to_string(),
+            })
+        );
+        Campaign::from_json_strict_schema(include_str!("example-response-cause.json")).unwrap();
+
+        // Left out, null, or saved before we asked
+        for json in [
+            include_str!("example-response.json"),
+            include_str!("example-response-null-amount.json"),
+            include_str!("example-response-totals.json"),
+        ] {
+            assert_eq!(Campaign::from_json(json).unwrap().cause, None);
+        }
+    }
+
+    #[test]
+    fn teams() {
+        let campaign = Campaign::from_json(include_str!("example-response-team.json")).unwrap();
+        assert_eq!(
+            campaign.team,
+            Some(Team {
+                name: "Relay FM".to_string(),
+                avatar: Some(Avatar::new(
+                    "https://assets.tiltify.com/uploads/team/thumbnail/relay-fm.png"
+                )),
+            })
+        );
+        Campaign::from_json_strict_schema(include_str!("example-response-team.json")).unwrap();
+
+        // Run by one person, or saved before we asked
+        for json in [
+            include_str!("example-response.json"),
+            include_str!("example-response-null-amount.json"),
+            include_str!("example-response-totals.json"),
+        ] {
+            assert_eq!(Campaign::from_json(json).unwrap().team, None);
+        }
+
+        // A team without a picture
+        let json = r#"{"data": {"campaign": {"name": "A", "team": {"name": "B", "avatar": null},
+            "totalAmountRaised": {"value": "1"}, "goal": {"value": "2"}}}}"#;
+        let team = Campaign::from_json(json).unwrap().team.unwrap();
+        assert_eq!(team.name, "B");
+        assert_eq!(team.avatar, None);
+    }
+
+    #[test]
+    fn only_published_is_taking_donations() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.id, Some(CampaignId::new("121745")));
+        assert!(campaign.is_live());
+
+        for status in [
+            None,
+            Some(CampaignStatus::Unpublished),
+            Some(CampaignStatus::Retired),
+            Some(CampaignStatus::Unknown),
+        ] {
+            campaign.status = status;
+            assert!(!campaign.is_live(), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn numeric_amounts() {
+        let json = include_str!("example-response-numeric.json");
+        let campaign = Campaign::from_json(json).unwrap();
+
+        // A float, a string, an integer, a float with no fraction, a string, and a null
+        assert_eq!(campaign.total_amount_raised.cents(), 2_266_340);
+        assert_eq!(campaign.goal.cents(), 33_333_333);
+        let amounts: Vec<_> = campaign.milestones.iter().map(|m| m.amount).collect();
+        assert_eq!(
+            amounts,
+            [
+                Some(Usd::new(75_000.)),
+                Some(Usd::new(55_000.)),
+                Some(Usd::new(20_000.)),
+                None
+            ]
+        );
+
+        let parse = |json: &str| serde_json::from_str::<Usd>(json).map(|usd| usd.cents());
+        assert_eq!(parse(r#"{"value": -5}"#).unwrap(), -500);
+        assert_eq!(parse(r#"{"value": 0.015}"#).unwrap(), 2);
+        let err = parse(r#"{"value": 18446744073709551615}"#).unwrap_err();
+        assert!(err.to_string().contains("too large"), "{}", err);
+        let err = parse(r#"{"value": true}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("a number, or a number in a string"),
+            "{}",
+            err
+        );
+    }
+
+    /// A milestone saved before its amount was set shouldn't fail the whole campaign
+    #[test]
+    fn null_milestone_amount() {
+        let json = include_str!("example-response-null-amount.json");
+        let campaign = Campaign::from_json(json).unwrap();
+
+        let amounts: Vec<_> = campaign.milestones.iter().map(|m| m.amount).collect();
+        assert_eq!(
+            amounts,
+            [Some(Usd::new(55_000.)), None, Some(Usd::new(20_000.))]
+        );
+
+        let err = Campaign::from_json_strict_schema(json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Milestone "Something secret" has no amount"#
+        );
+    }
+
+    /// Statuses we've never seen shouldn't fail the whole campaign
+    #[test]
+    fn unknown_status() {
+        let status: CampaignStatus = serde_json::from_str(r#""archived""#).unwrap();
+        assert_eq!(status, CampaignStatus::Unknown);
+    }
+
+    #[test]
+    fn rounding_policies() {
+        use RoundingPolicy::*;
+
+        // (amount, [HalfUp, HalfEven, Floor, Ceiling]) in cents
+        #[rustfmt::skip]
+        let cases: &[(f64, [i64; 4])] = &[
+            // The classic ties, at cent scale
+            (0.005,     [1, 0, 0, 1]),
+            (0.015,     [2, 2, 1, 2]),
+            (0.025,     [3, 2, 2, 3]),
+            (-0.005,    [-1, 0, -1, 0]),
+            (-0.015,    [-2, -2, -2, -1]),
+            // ... and whole dollars, which aren't ties at all
+            (0.5,       [50, 50, 50, 50]),
+            (1.5,       [150, 150, 150, 150]),
+            (2.5,       [250, 250, 250, 250]),
+            // Not ties, but close enough to whole cents that float noise could tip them over
+            (22_663.40, [2266340, 2266340, 2266340, 2266340]),
+            (0.1 + 0.2, [30, 30, 30, 30]),
+            // Clearly not ties
+            (0.004,     [0, 0, 0, 1]),
+            (0.006,     [1, 1, 0, 1]),
+            (1234.999,  [123500, 123500, 123499, 123500]),
+        ];
+
+        for &(amount, expected) in cases {
+            for (policy, expected) in [HalfUp, HalfEven, Floor, Ceiling].iter().zip(&expected) {
+                assert_eq!(
+                    policy.round_cents(amount),
+                    *expected,
+                    "{:?} rounding ${}",
+                    policy,
+                    amount
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let sum = Usd::new(0.1) + Usd::new(0.2);
+        assert_eq!(sum.to_string(), "$0.30");
+        assert_eq!(sum, Usd::new(0.3));
+
+        let left = Usd::new(55_000.) - Usd::new(22_663.40);
+        assert_eq!(left.to_string(), "$32,336.60");
+
+        let mut total = Usd::new(22_663.40);
+        total += Usd::new(0.01);
+        assert_eq!(total.to_string(), "$22,663.41");
+        total -= Usd::new(0.02);
+        assert_eq!(total.to_string(), "$22,663.39");
+
+        assert_eq!((Usd::new(19.99) * 3.).to_string(), "$59.97");
+        assert_eq!((Usd::new(100.) / 3.).to_string(), "$33.33");
+    }
+
+    #[test]
+    fn subtracting_past_zero() {
+        let past = Usd::new(20_000.) - Usd::new(22_663.40);
+        assert!(past.usd() < 0.);
+        assert_eq!(past, Usd::new(-2_663.40));
+
+        assert_eq!(
+            Usd::new(20_000.).saturating_sub(Usd::new(22_663.40)),
+            Usd::new(0.)
+        );
+        assert_eq!(
+            Usd::new(0.1).saturating_sub(Usd::new(0.3)).to_string(),
+            "$0.00"
+        );
+        assert_eq!(
+            Usd::new(0.3).saturating_sub(Usd::new(0.1)).to_string(),
+            "$0.20"
+        );
+    }
+
+    #[test]
+    fn displays_negatives() {
+        let refund = Usd::new(-1_234.56);
+
+        assert_eq!(Usd::new(-0.01).to_string(), "-$0.01");
+        assert_eq!(refund.to_string(), "-$1,234.56");
+        assert_eq!(Usd::ZERO.to_string(), "$0.00");
+        assert_eq!(Usd::new(-0.).to_string(), "$0.00");
+
+        assert_eq!(format!("{:#}", refund), "($1,234.56)");
+        assert_eq!(format!("{:#}", Usd::new(1_234.56)), "$1,234.56");
+
+        // The sign is padded along with the rest
+        assert_eq!(format!("{:>12}", refund), "  -$1,234.56");
+        assert_eq!(format!("{:12}", Usd::new(-0.01)), "      -$0.01");
+        assert_eq!(format!("{:#13}", refund), "  ($1,234.56)");
+
+        // Refunds can make Tiltify send a negative, and both directions keep it
+        let json = serde_json::json!({ "currency": "USD", "value": "-1234.56" });
+        assert_eq!(serde_json::from_value::<Usd>(json).unwrap(), refund);
+        assert_eq!(refund.to_string().parse::<Usd>(), Ok(refund));
+    }
+
+    #[test]
+    fn format_spec() {
+        let raised = Usd::new(22_663.40);
+        let refund = Usd::new(-1_234.56);
+
+        assert_eq!(format!("{:>12}", raised), "  $22,663.40");
+        assert_eq!(format!("{:12}", raised), "  $22,663.40");
+        assert_eq!(format!("{:<12}", raised), "$22,663.40  ");
+        assert_eq!(format!("{:^14}", raised), "  $22,663.40  ");
+        assert_eq!(format!("{:*>12}", raised), "**$22,663.40");
+        assert_eq!(format!("{:-^15}", raised), "--$22,663.40---");
+
+        assert_eq!(format!("{:.0}", raised), "$22,663");
+        assert_eq!(format!("{:.1}", raised), "$22,663.4");
+        assert_eq!(format!("{:.3}", raised), "$22,663.400");
+        assert_eq!(format!("{:.0}", Usd::new(999.50)), "$1,000");
+        assert_eq!(format!("{:.0}", Usd::new(2.50)), "$2");
+        assert_eq!(format!("{:.1}", Usd::new(0.95)), "$1.0");
+
+        assert_eq!(format!("{:<12}", refund), "-$1,234.56  ");
+        assert_eq!(format!("{:^14}", refund), "  -$1,234.56  ");
+        assert_eq!(format!("{:.0}", refund), "-$1,235");
+        assert_eq!(format!("{:#.0}", refund), "($1,235)");
+        assert_eq!(format!("{:_>#10.0}", refund), "__($1,235)");
+        // Rounding to nothing drops the sign
+        assert_eq!(format!("{:.0}", Usd::new(-0.40)), "$0");
+    }
+
+    #[test]
+    fn displays_with_locales() {
+        let raised = Usd::new(1_222_663.40);
+        let show = |locale| raised.display_with_locale(locale).to_string();
+
+        assert_eq!(show(Locale::en), "$1,222,663.40");
+        assert_eq!(show(Locale::en), raised.to_string());
+        assert_eq!(show(Locale::de), "$1.222.663,40");
+        assert_eq!(show(Locale::fr), "$1\u{202f}222\u{202f}663,40");
+
+        // Padding counts characters, so a multi-byte separator is still one column
+        let padded = |locale| format!("{:>16}", raised.display_with_locale(locale));
+        assert_eq!(padded(Locale::en), "   $1,222,663.40");
+        assert_eq!(padded(Locale::de), "   $1.222.663,40");
+        assert_eq!(padded(Locale::fr), "   $1\u{202f}222\u{202f}663,40");
+
+        let refund = Usd::new(-1_234.5).display_with_locale(Locale::de);
+        assert_eq!(format!("{:11}", refund), " -$1.234,50");
+        assert_eq!(format!("{:#}", refund), "($1.234,50)");
+    }
+
+    #[test]
+    fn compact() {
+        let compact = |amount: f64| Usd::new(amount).compact();
+
+        assert_eq!(compact(0.), "$0.00");
+        assert_eq!(compact(999.99), "$999.99");
+        assert_eq!(compact(1_000.), "$1K");
+        assert_eq!(compact(1_049.99), "$1K");
+        assert_eq!(compact(1_050.), "$1.1K");
+        assert_eq!(compact(22_663.40), "$22.7K");
+        assert_eq!(compact(55_000.), "$55K");
+        assert_eq!(compact(999_949.99), "$999.9K");
+        assert_eq!(compact(999_950.), "$1M");
+        assert_eq!(compact(1_250_000.), "$1.3M");
+        assert_eq!(compact(999_950_000.), "$1B");
+        assert_eq!(compact(1_234_567_890_000.), "$1,234.6B");
+        assert_eq!(compact(-22_663.40), "-$22.7K");
+        assert_eq!(compact(-5.), "-$5.00");
+        assert_eq!(Usd::from_cents(i64::MIN).compact(), "-$92,233,720.4B");
+    }
+
+    /// Rounding happens once, to whole cents, so a carry reaches the dollars
+    #[test]
+    fn display_carries_into_dollars() {
+        let show = |amount: f64| Usd::new(amount).to_string();
+
+        assert_eq!(show(0.994), "$0.99");
+        assert_eq!(show(0.995), "$1.00");
+        assert_eq!(show(0.9999), "$1.00");
+        assert_eq!(show(1_234.994), "$1,234.99");
+        assert_eq!(show(1_234.995), "$1,235.00");
+        assert_eq!(show(1_234.999), "$1,235.00");
+        assert_eq!(show(999.994), "$999.99");
+        assert_eq!(show(999.995), "$1,000.00");
+        assert_eq!(show(999_999.999), "$1,000,000.00");
+
+        // Half-even only matters on exact ties, and 0.985 is one: 98.5 cents rounds to 98
+        assert_eq!(show(0.985), "$0.98");
+        assert_eq!(
+            Usd::new_rounded(0.985, RoundingPolicy::HalfUp).to_string(),
+            "$0.99"
+        );
+    }
+
+    #[test]
+    fn sums() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+
+        let total: Usd = campaign.milestones.iter().filter_map(|m| m.amount).sum();
+        assert_eq!(total.cents(), 34_606_044);
+        assert_eq!(total.to_string(), "$346,060.44");
+
+        // Any missing amount makes the whole sum unknown, with std's Option impl
+        let maybe: Option<Usd> = campaign.milestones.iter().map(|m| m.amount).sum();
+        assert_eq!(maybe, Some(total));
+
+        let amounts = [Usd::new(0.1); 10];
+        assert_eq!(amounts.iter().sum::<Usd>(), Usd::new(1.));
+        assert_eq!(Vec::<Usd>::new().into_iter().sum::<Usd>(), Usd::ZERO);
+    }
+
+    #[test]
+    fn cents_are_exact() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.total_amount_raised.cents(), 2_266_340);
+        assert_eq!(
+            Usd::from_cents(campaign.total_amount_raised.cents()),
+            campaign.total_amount_raised
+        );
+
+        // Float noise below a millionth of a cent is ignored, whichever way it rounds
+        for &policy in &[
+            RoundingPolicy::HalfUp,
+            RoundingPolicy::HalfEven,
+            RoundingPolicy::Floor,
+            RoundingPolicy::Ceiling,
+        ] {
+            assert_eq!(Usd::new_rounded(22_663.40, policy).cents(), 2_266_340);
+        }
+        assert_eq!(Usd::new_rounded(0.011, RoundingPolicy::Floor).cents(), 1);
+        assert_eq!(Usd::new_rounded(0.011, RoundingPolicy::Ceiling).cents(), 2);
+
+        assert_eq!(Usd::new(1e30).cents(), i64::MAX);
+        assert_eq!(Usd::new(-1e30).cents(), i64::MIN);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let raised = Usd::new(22_663.40);
+        let milestone = Usd::new(20_000.);
+
+        assert_eq!(milestone.checked_sub(raised), Some(Usd::new(-2_663.40)));
+        assert_eq!(milestone.saturating_sub(raised), Usd::ZERO);
+        assert_eq!(raised.checked_add(milestone), Some(Usd::new(42_663.40)));
+
+        assert_eq!(Usd::MAX.checked_add(Usd::from_cents(1)), None);
+        assert_eq!(
+            (Usd::MAX - Usd::from_cents(1)).checked_add(Usd::from_cents(1)),
+            Some(Usd::MAX)
+        );
+        assert_eq!(
+            Usd::from_cents(i64::MIN).checked_sub(Usd::from_cents(1)),
+            None
+        );
+        assert_eq!(Usd::MAX.saturating_sub(Usd::from_cents(i64::MIN)), Usd::MAX);
+        assert_eq!(
+            Usd::from_cents(i64::MIN).saturating_sub(Usd::MAX),
+            Usd::ZERO
+        );
+        assert_eq!(Usd::MAX.to_string(), "$92,233,720,368,547,758.07");
+    }
+
+    #[test]
+    fn sums_saturate() {
+        let cent = Usd::from_cents(1);
+
+        assert_eq!([Usd::MAX, cent].iter().sum::<Usd>(), Usd::MAX);
+        assert_eq!(vec![Usd::MAX; 3].into_iter().sum::<Usd>(), Usd::MAX);
+        assert_eq!(
+            [Usd::MIN, Usd::from_cents(-1)].iter().sum::<Usd>(),
+            Usd::MIN
+        );
+        assert_eq!(
+            [Usd::MAX, cent]
+                .iter()
+                .try_fold(Usd::ZERO, |total, &a| total.checked_add(a)),
+            None
+        );
+    }
+
+    /// The operators stop at the ends of the range instead of overflowing
+    #[test]
+    fn operators_saturate() {
+        let cent = Usd::from_cents(1);
+
+        assert_eq!(Usd::MAX + cent, Usd::MAX);
+        assert_eq!(Usd::MAX + Usd::MAX, Usd::MAX);
+        assert_eq!(Usd::MIN + Usd::MIN, Usd::MIN);
+        assert_eq!(Usd::MIN - cent, Usd::MIN);
+        assert_eq!(Usd::MAX - Usd::MIN, Usd::MAX);
+        assert_eq!(Usd::MIN - Usd::MAX, Usd::MIN);
+
+        let mut total = Usd::MAX - cent;
+        total += cent;
+        total += cent;
+        assert_eq!(total, Usd::MAX);
+        total -= Usd::MAX;
+        total -= Usd::MAX;
+        total -= Usd::MAX;
+        assert_eq!(total, Usd::MIN);
+
+        // Nowhere near the ends, they're exact
+        assert_eq!(Usd::MAX + Usd::MIN, Usd::from_cents(-1));
+        assert_eq!(Usd::MIN + cent - cent, Usd::MIN);
+    }
+
+    #[test]
+    fn compares_whole_cents() {
+        assert_eq!(Usd::new(1.001), Usd::new(1.004));
+        assert_ne!(Usd::new(1.004), Usd::new(1.006));
+        assert!(Usd::new(1.004) < Usd::new(1.006));
+        assert_eq!(
+            Usd::new(0.1) + Usd::new(0.2),
+            Usd::new(0.3),
+            "0.1 + 0.2 is 0.30000000000000004"
+        );
+
+        let mut raised = std::collections::BTreeMap::new();
+        raised.insert(Usd::new(10.001), "first");
+        raised.insert(Usd::new(10.), "second");
+        assert_eq!(raised.len(), 1);
+    }
+
+    #[test]
+    fn hash_map_keys() {
+        use std::collections::HashMap;
+
+        let parsed: Usd = serde_json::from_str(r#"{"value": "22663.40"}"#).unwrap();
+        let mut notes = HashMap::new();
+        notes.insert(Usd::new(22_663.40), "raised so far");
+        assert_eq!(notes.get(&parsed), Some(&"raised so far"));
+        assert_eq!(
+            notes.get(&"$22,663.40".parse().unwrap()),
+            Some(&"raised so far")
+        );
+
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let by_amount: HashMap<Usd, &str> = campaign
+            .milestones
+            .iter()
+            .filter_map(|m| Some((m.amount?, m.description.as_str())))
+            .collect();
+        assert_eq!(
+            by_amount[&Usd::new(55_000.)],
+            "Stephen dissembles his NeXTCube on stream"
+        );
+    }
+
+    /// No more multiplying by 100 and casting to sort
+    #[test]
+    fn sorts_milestones() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        campaign.milestones.sort_by_key(|m| m.amount);
+
+        let amounts: Vec<_> = campaign
+            .milestones
+            .iter()
+            .map(|m| m.amount.unwrap().to_string())
+            .collect();
+        assert_eq!(
+            amounts,
+            ["$20,000.00", "$55,000.00", "$75,000.00", "$196,060.44"]
+        );
+    }
+
+    #[test]
+    fn non_finite_amounts_are_clamped() {
+        assert_eq!(Usd::new(f64::NAN), Usd::ZERO);
+        assert_eq!(Usd::new(f64::INFINITY), Usd::MAX);
+        assert_eq!(Usd::new(f64::NEG_INFINITY), Usd::MIN);
+        assert_eq!(
+            Usd::new_rounded(f64::NAN, RoundingPolicy::Ceiling),
+            Usd::ZERO
+        );
+        assert_eq!(Usd::new(22_663.40) * f64::NAN, Usd::ZERO);
+        assert_eq!(Usd::new(22_663.40) / 0., Usd::MAX);
+
+        assert_eq!(Usd::try_new(f64::NAN), None);
+        assert_eq!(Usd::try_new(f64::INFINITY), None);
+        assert_eq!(Usd::try_new(f64::NEG_INFINITY), None);
+        assert_eq!(Usd::try_new(22_663.40), Some(Usd::new(22_663.40)));
+    }
+
+    #[test]
+    fn non_finite_amounts_dont_parse() {
+        for value in &["NaN", "inf", "-infinity"] {
+            let json = format!(r#"{{"currency": "USD", "value": "{}"}}"#, value);
+            let err = serde_json::from_str::<Usd>(&json).unwrap_err();
+            assert!(err.to_string().contains("isn't a finite number"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn decimal_strings_parse_exactly() {
+        let parse = |s: &str| serde_json::from_value::<Usd>(serde_json::json!({ "value": s }));
+
+        assert_eq!(parse("22663.40").unwrap().cents(), 2_266_340);
+        assert_eq!(parse("0.1").unwrap().cents(), 10);
+        assert_eq!(parse("5").unwrap().cents(), 500);
+        assert_eq!(parse("-1.50").unwrap().cents(), -150);
+        // Fractions of a cent round half to even, exactly
+        assert_eq!(parse("0.005").unwrap().cents(), 0);
+        assert_eq!(parse("0.015").unwrap().cents(), 2);
+        assert_eq!(parse("0.0050001").unwrap().cents(), 1);
+        assert_eq!(parse("2.675").unwrap().cents(), 268);
+        // Not a plain decimal, so through a float
+        assert_eq!(parse("1e3").unwrap().cents(), 100_000);
+        assert!(parse("12,34").is_err());
+    }
+
+    #[test]
+    fn parses_dollar_strings() {
+        let cents = |s: &str| s.parse::<Usd>().map(|usd| usd.cents());
+
+        assert_eq!(cents("$1,234.56"), Ok(123_456));
+        assert_eq!(cents("1234.56"), Ok(123_456));
+        assert_eq!(cents("1,234"), Ok(123_400));
+        assert_eq!(cents("$5"), Ok(500));
+        assert_eq!(cents("$0.5"), Ok(50));
+        assert_eq!(cents(" $1,000,000.00 "), Ok(100_000_000));
+        assert_eq!(cents("-$2,663.40"), Ok(-266_340));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        use ParseUsdError::*;
+
+        for (bad, why) in &[
+            ("", NoDigits),
+            ("$", NoDigits),
+            ("$.50", NoDigits),
+            ("$1.2.3", MultipleDecimalPoints),
+            ("12,34.56", MisplacedComma),
+            ("1234,567", MisplacedComma),
+            (",123", MisplacedComma),
+            ("1,234,", MisplacedComma),
+            ("1.23,4", MisplacedComma),
+            ("1.234", TooManyDecimals),
+            ("USD 5", UnexpectedCharacter('U')),
+            ("$-5", UnexpectedCharacter('-')),
+            ("1e3", UnexpectedCharacter('e')),
+            ("99999999999999999999", TooLarge),
+        ] {
+            assert_eq!(bad.parse::<Usd>(), Err(*why), "{:?}", bad);
+        }
+
+        let err = "12,34.56".parse::<Usd>().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "invalid dollar amount: commas can only separate groups of three digits"
+        );
+    }
+
+    #[test]
+    fn milestones_round_trip_through_strings() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let amounts = campaign
+            .milestones
+            .iter()
+            .filter_map(|m| m.amount)
+            .chain(vec![campaign.total_amount_raised, campaign.goal]);
+
+        for amount in amounts {
+            assert_eq!(amount.to_string().parse::<Usd>(), Ok(amount));
+        }
+    }
+
+    #[test]
+    fn serializes_like_the_api() {
+        for response in &[
+            include_str!("example-response.json"),
+            include_str!("example-response-null-amount.json"),
+        ] {
+            let campaign = Campaign::from_json(response).unwrap();
+            let json = serde_json::to_string(&campaign).unwrap();
+            assert_eq!(serde_json::from_str::<Campaign>(&json).unwrap(), campaign);
+
+            // Amounts are written exactly as Tiltify sent them
+            let sent: Value = serde_json::from_str(response).unwrap();
+            let sent = &sent["data"]["campaign"];
+            let written: Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(written["totalAmountRaised"], sent["totalAmountRaised"]);
+            assert_eq!(written["goal"], sent["goal"]);
+            for (written, sent) in written["milestones"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .zip(sent["milestones"].as_array().unwrap())
+            {
+                assert_eq!(written["amount"], sent["amount"]);
+                assert_eq!(written["name"], sent["name"]);
+            }
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Usd::from_cents(2_266_340)).unwrap(),
+            r#"{"currency":"USD","value":"22663.40"}"#
+        );
+    }
+
+    /// The ends of the range can't be negated as an `i64`, so they're written without doing so
+    #[test]
+    fn serializes_the_extremes() {
+        assert_eq!(
+            serde_json::to_string(&Usd::MIN).unwrap(),
+            r#"{"currency":"USD","value":"-92233720368547758.08"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Usd::MAX).unwrap(),
+            r#"{"currency":"USD","value":"92233720368547758.07"}"#
+        );
+        assert_eq!(Usd::ZERO - Usd::MAX - Usd::new(1.), Usd::MIN);
+
+        let mut campaign = Campaign::builder().build();
+        campaign.total_amount_raised = Usd::MIN;
+        campaign.goal = Usd::MAX;
+        let json = campaign.to_canonical_json();
+        assert!(
+            json.contains(r#""value": "-92233720368547758.08""#),
+            "{}",
+            json
+        );
+        assert!(
+            json.contains(r#""value": "92233720368547758.07""#),
+            "{}",
+            json
+        );
+
+        let converted = Usd::MIN
+            .convert(&StaticRates::new(), Currency::Usd)
+            .unwrap();
+        assert_eq!(converted.value, "-92233720368547758.08");
+    }
+
+    /// Display, then parse the digits, for a spread of amounts up to $100 million
+    #[test]
+    fn display_round_trips() {
+        // A fixed linear congruential generator, so failures are reproducible
+        let mut state: u64 = 0x5eed;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state >> 33
+        };
+
+        let max_cents = 100_000_000 * 100;
+        let cents = (0..5_000)
+            .map(|_| next() as i64 % (max_cents + 1))
+            .chain(vec![0, 1, 99, 100, 2_266_340, max_cents]);
+
+        for cents in cents {
+            let usd = Usd::from_cents(cents);
+            let shown = usd.to_string();
+            let digits: String = shown.chars().filter(|&c| c != '$' && c != ',').collect();
+            let parsed: Usd =
+                serde_json::from_value(serde_json::json!({ "value": digits })).unwrap();
+
+            assert_eq!(parsed, usd, "{}", shown);
+            assert_eq!(Usd::new(usd.usd()), usd, "{}", shown);
+            assert_eq!(shown.parse::<Usd>(), Ok(usd), "{}", shown);
+        }
+    }
+
+    #[test]
+    fn display_rounds_half_even() {
+        assert_eq!(Usd::new(0.125).to_string(), "$0.12");
+        assert_eq!(Usd::new(0.135).to_string(), "$0.14");
+        assert_eq!(
+            Usd::new_rounded(0.125, RoundingPolicy::HalfUp).to_string(),
+            "$0.13"
+        );
+
+        // Carries make it into the dollars
+        assert_eq!(Usd::new(0.999).to_string(), "$1.00");
+        assert_eq!(Usd::new(22_663.40).to_string(), "$22,663.40");
+    }
+
+    /// A response with exactly the fields we select, used for the strict schema checks
+    const STRICT_RESPONSE: &str = r#"{
+        "data": {
+            "campaign": {
+                "id": 121745,
+                "name": "Relay FM for St. Jude 2021",
+                "description": "St Jude!",
+                "status": "published",
+                "totalAmountRaised": { "currency": "USD", "value": "22663.40" },
+                "goal": { "currency": "USD", "value": "333333.33" },
+                "milestones": [
+                    {
+                        "name": "Stephen & Myke go to space via KSP",
+                        "amount": { "currency": "USD", "value": "75000.00" }
+                    }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn strict_schema_accepts_exact_response() {
+        let strict = Campaign::from_json_strict_schema(STRICT_RESPONSE).unwrap();
+        let lenient = Campaign::from_json(STRICT_RESPONSE).unwrap();
+
+        assert_eq!(strict, lenient);
+    }
+
+    /// Inject an unknown field at every level and make sure we name it
+    #[test]
+    fn strict_schema_names_unknown_fields() {
+        // (field to inject, JSON pointer to the object to put it in)
+        let injections = [
+            ("rootDrift", ""),
+            ("dataDrift", "/data"),
+            ("campaignDrift", "/data/campaign"),
+            ("raisedDrift", "/data/campaign/totalAmountRaised"),
+            ("goalDrift", "/data/campaign/goal"),
+            ("milestoneDrift", "/data/campaign/milestones/0"),
+            ("milestoneAmountDrift", "/data/campaign/milestones/0/amount"),
+        ];
+
+        for (field, at) in injections {
+            let mut value: serde_json::Value = serde_json::from_str(STRICT_RESPONSE).unwrap();
+            value.pointer_mut(at).unwrap()[field] = serde_json::json!("surprise");
+
+            let json = value.to_string();
+            let err = Campaign::from_json_strict_schema(&json).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("unknown field `{}`", field)),
+                "{}: {}",
+                field,
+                err
+            );
+
+            // ... but normal parsing doesn't care
+            Campaign::from_json(&json).unwrap();
+        }
+    }
+
+    #[test]
+    fn strict_schema_checks_errors_too() {
+        let json = r#"{"errors": [{"message": "nope", "locations": [{"line": 1, "column": 2, "file": "?"}]}]}"#;
+        let err = Campaign::from_json_strict_schema(json).unwrap_err();
+        assert!(err.to_string().contains("unknown field `file`"), "{}", err);
+    }
+
+    /// Ids come back as numbers today, but strings should work the same way
+    #[test]
+    fn campaign_id_from_number_or_string() {
+        let from_number: CampaignId = serde_json::from_str("121745").unwrap();
+        let from_string: CampaignId = serde_json::from_str(r#""121745""#).unwrap();
+
+        assert_eq!(from_number, from_string);
+        assert_eq!(from_number.as_str(), "121745");
+    }
+
+    /// Strings, bytes, readers, and parsed JSON all give the same campaign, or the same error
+    #[test]
+    fn entry_points_agree() {
+        let responses = [
+            include_str!("example-response.json"),
+            include_str!("example-response-unpublished.json"),
+            include_str!("example-response-null-amount.json"),
+            // Escapes mean the amount can't be borrowed from the input
+            r#"{"data": {"campaign": {"name": "\u0041", "description": "",
+                "totalAmountRaised": {"value": "\u0031.5"}, "goal": {"value": "2"}}}}"#,
+            r#"{"errors": [{"message": "nope", "locations": [{"line": 1, "column": 2}]}]}"#,
+            r#"{"data": {"campaign": {"name": "A", "description": "",
+                "totalAmountRaised": {"value": "lots"}, "goal": {"value": "2"}}}}"#,
+        ];
+
+        for json in &responses {
+            let value: Value = serde_json::from_str(json).unwrap();
+            let results = [
+                Campaign::from_json(json),
+                Campaign::from_slice(json.as_bytes()),
+                Campaign::from_reader(json.as_bytes()),
+                Campaign::try_from(&value),
+                Campaign::try_from(value.clone()),
+            ];
+
+            match &results[0] {
+                Ok(expected) => {
+                    for result in &results[1..] {
+                        assert_eq!(result.as_ref().unwrap(), expected, "{}", json);
+                    }
+                }
+                Err(_) => assert!(results.iter().all(Result::is_err), "{}", json),
+            }
+        }
+
+        let escaped = Campaign::from_slice(responses[3].as_bytes()).unwrap();
+        assert_eq!(escaped.total_amount_raised, Usd::new(1.5));
+    }
+
     /// Verify that the live API JSON from the API matches our serde model
     #[test]
     fn live_response() {
@@ -0,0 +1,296 @@
+use crate::completion::{CompletionRules, Context};
+use crate::{Campaign, Milestone, Usd};
+
+/// How to order milestones in a [`MilestonePage`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum MilestoneSort {
+    /// Cheapest first
+    #[default]
+    Amount,
+
+    /// Closest to being reached first, then completed ones from most recently reached
+    Remaining,
+}
+
+/// Which milestones to list before the others
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompletedPlacement {
+    /// Reached milestones, then the rest
+    First,
+
+    /// Milestones not reached yet, then the rest
+    Last,
+}
+
+/// Options for [`Campaign::milestone_page`]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ListOptions {
+    /// The order to list milestones in
+    pub sort: MilestoneSort,
+
+    /// Group completed milestones together, instead of wherever `sort` puts them
+    pub completed: Option<CompletedPlacement>,
+
+    /// How many milestones per page, or `None` for all of them
+    pub limit: Option<usize>,
+
+    /// Which page to show, counting from 0
+    pub page: usize,
+}
+
+/// One page of a campaign's milestones
+#[derive(Clone, Debug, PartialEq)]
+pub struct MilestonePage<'a> {
+    /// The milestones on this page, in order
+    pub milestones: Vec<&'a Milestone>,
+
+    /// How many milestones come after this page
+    pub more: usize,
+
+    /// How many milestones there are in total
+    pub total: usize,
+}
+
+impl Campaign {
+    /// List a page of milestones, ordered by `options`
+    ///
+    /// Ties always break the same way: by amount, then description, then the order the API
+    /// returned them in. So repeated calls never shuffle milestones around. Milestones that don't
+    /// have an amount yet always come last.
+    ///
+    /// A milestone counts as completed once the total reaches its amount.
+    pub fn milestone_page(&self, options: &ListOptions) -> MilestonePage<'_> {
+        self.milestone_page_with(options, &CompletionRules::new(), None)
+    }
+
+    /// Every milestone, cheapest first
+    ///
+    /// [`Campaign::milestones`] stays in whatever order the API sent. This is the same order as
+    /// a [`milestone_page`](Campaign::milestone_page) with the default options, so milestones
+    /// without an amount yet come last.
+    pub fn sorted_milestones(&self) -> Vec<&Milestone> {
+        self.milestone_page(&ListOptions::default()).milestones
+    }
+
+    /// Like [`milestone_page`](Campaign::milestone_page), but deciding which milestones are
+    /// completed with `rules`
+    ///
+    /// `event_total` is passed on to the rules, see [`Context::event_total`].
+    pub fn milestone_page_with(
+        &self,
+        options: &ListOptions,
+        rules: &CompletionRules,
+        event_total: Option<Usd>,
+    ) -> MilestonePage<'_> {
+        let context = Context {
+            campaign: self,
+            event_total,
+        };
+        let raised = self.total_amount_raised.cents();
+
+        let mut milestones: Vec<(usize, &Milestone)> = self.milestones.iter().enumerate().collect();
+        milestones.sort_by(|(ai, a), (bi, b)| {
+            let key = |m: &Milestone| {
+                let amount = m.amount.map(|amount| amount.cents());
+                let order = match (options.sort, amount) {
+                    (_, None) => i64::MAX,
+                    (MilestoneSort::Amount, Some(amount)) => amount,
+                    // Pending milestones sort by how far away they are, and completed ones after
+                    // all of them, most recently reached first
+                    (MilestoneSort::Remaining, Some(amount)) if amount > raised => amount - raised,
+                    (MilestoneSort::Remaining, Some(amount)) => i64::MAX / 2 + (raised - amount),
+                };
+                let done = rules.is_completed(m, &context);
+                let group = match options.completed {
+                    Some(CompletedPlacement::First) => u8::from(!done),
+                    Some(CompletedPlacement::Last) => u8::from(done),
+                    None => 0,
+                };
+                (group, order, amount)
+            };
+
+            key(a)
+                .cmp(&key(b))
+                .then_with(|| a.description.cmp(&b.description))
+                .then(ai.cmp(bi))
+        });
+
+        let total = milestones.len();
+        let (start, end) = match options.limit {
+            Some(limit) if limit > 0 => {
+                let start = options.page.saturating_mul(limit).min(total);
+                (start, start.saturating_add(limit).min(total))
+            }
+            _ => (0, total),
+        };
+
+        MilestonePage {
+            milestones: milestones[start..end].iter().map(|&(_, m)| m).collect(),
+            more: total - end,
+            total,
+        }
+    }
+}
+
+impl Milestone {
+    /// Whether a campaign that has raised `raised` has reached this milestone
+    ///
+    /// Milestones without an amount are never completed.
+    pub fn is_completed(&self, raised: Usd) -> bool {
+        self.amount.is_some_and(|amount| amount <= raised)
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    /// A ladder with lots of ties: names are "<amount>-<letter>"
+    fn campaign() -> Campaign {
+        let milestones = [
+            (300., "300-b"),
+            (100., "100-b"),
+            (200., "200-a"),
+            (100., "100-a"),
+            (300., "300-a"),
+            (400., "400-a"),
+            (100., "100-a"),
+            (500., "500-a"),
+        ];
+
+        milestones
+            .iter()
+            .fold(
+                Campaign::builder().name("Test").raised(300.).goal(1_000.),
+                |builder, &(amount, description)| builder.milestone(description, amount),
+            )
+            .unpriced_milestone("unset")
+            .build()
+    }
+
+    fn names(page: &MilestonePage) -> Vec<String> {
+        page.milestones
+            .iter()
+            .map(|m| m.description.clone())
+            .collect()
+    }
+
+    fn list(options: ListOptions) -> Vec<String> {
+        names(&campaign().milestone_page(&options))
+    }
+
+    #[test]
+    fn by_amount() {
+        assert_eq!(
+            list(ListOptions::default()),
+            ["100-a", "100-a", "100-b", "200-a", "300-a", "300-b", "400-a", "500-a", "unset"]
+        );
+    }
+
+    #[test]
+    fn sorted() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let amounts: Vec<_> = campaign
+            .sorted_milestones()
+            .iter()
+            .map(|m| m.amount.unwrap().cents())
+            .collect();
+        assert_eq!(amounts, [2_000_000, 5_500_000, 7_500_000, 19_606_044]);
+
+        // The API's order is left alone
+        assert_eq!(campaign.milestones[0].amount, Some(Usd::new(75_000.)));
+    }
+
+    /// Identical milestones keep the order the API returned them in
+    #[test]
+    fn ties_are_stable() {
+        let campaign = campaign();
+        let page = campaign.milestone_page(&ListOptions::default());
+
+        let first = page.milestones[0] as *const Milestone;
+        let second = page.milestones[1] as *const Milestone;
+        assert_eq!(first, &campaign.milestones[3] as *const Milestone);
+        assert_eq!(second, &campaign.milestones[6] as *const Milestone);
+    }
+
+    #[test]
+    fn by_remaining() {
+        let options = ListOptions {
+            sort: MilestoneSort::Remaining,
+            ..ListOptions::default()
+        };
+
+        assert_eq!(
+            list(options),
+            ["400-a", "500-a", "300-a", "300-b", "200-a", "100-a", "100-a", "100-b", "unset"]
+        );
+    }
+
+    #[test]
+    fn grouped() {
+        let pending_first = ListOptions {
+            completed: Some(CompletedPlacement::Last),
+            ..ListOptions::default()
+        };
+        assert_eq!(
+            list(pending_first),
+            ["400-a", "500-a", "unset", "100-a", "100-a", "100-b", "200-a", "300-a", "300-b"]
+        );
+
+        let completed_first = ListOptions {
+            completed: Some(CompletedPlacement::First),
+            ..ListOptions::default()
+        };
+        assert_eq!(
+            list(completed_first),
+            ["100-a", "100-a", "100-b", "200-a", "300-a", "300-b", "400-a", "500-a", "unset"]
+        );
+    }
+
+    #[test]
+    fn grouped_with_rules() {
+        let mut rules = CompletionRules::new();
+        rules.insert("500-a", crate::completion::Manual(true));
+        rules.insert("100-b", crate::completion::Manual(false));
+
+        let options = ListOptions {
+            completed: Some(CompletedPlacement::First),
+            ..ListOptions::default()
+        };
+        let campaign = campaign();
+        let page = campaign.milestone_page_with(&options, &rules, None);
+
+        assert_eq!(
+            names(&page),
+            ["100-a", "100-a", "200-a", "300-a", "300-b", "500-a", "100-b", "400-a", "unset"]
+        );
+    }
+
+    #[test]
+    fn pages() {
+        let campaign = campaign();
+        let page = |page, limit| {
+            campaign.milestone_page(&ListOptions {
+                limit,
+                page,
+                ..ListOptions::default()
+            })
+        };
+
+        let first = page(0, Some(4));
+        assert_eq!(names(&first), ["100-a", "100-a", "100-b", "200-a"]);
+        assert_eq!((first.more, first.total), (5, 9));
+
+        let last = page(2, Some(4));
+        assert_eq!(names(&last), ["unset"]);
+        assert_eq!(last.more, 0);
+
+        let past_the_end = page(7, Some(4));
+        assert!(past_the_end.milestones.is_empty());
+        assert_eq!(past_the_end.more, 0);
+
+        // No limit, or a limit of 0, is everything
+        assert_eq!(page(0, None).milestones.len(), 9);
+        assert_eq!(page(3, Some(0)).milestones.len(), 9);
+    }
+}
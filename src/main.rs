@@ -1,56 +1,465 @@
 use color_eyre::Report;
+use tracing_subscriber::filter::LevelFilter;
 
-use relay_st_jude::{Campaign, Usd};
+use relay_st_jude::duration::HumanDuration;
+use relay_st_jude::simulate::{self, Assumptions, RateWindow, Sample};
+use relay_st_jude::{
+    relay, Campaign, CampaignQuery, CampaignStatus, CompletedPlacement, ListOptions, MilestoneSort,
+    OutputKind, Usd,
+};
 
-fn percent(a: Usd, b: Usd) -> String {
-    format!("{:2.1}%", 100.0 * a.usd() / b.usd())
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How far ahead `project` will look: a year, which is longer than any campaign runs
+const MAX_HORIZON: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Command line options
+///
+/// There are few enough of these that we parse them by hand.
+#[derive(Debug, Default)]
+struct Args {
+    /// `-q` lowers this, each `-v` raises it
+    verbosity: i8,
+
+    command: Command,
+
+    /// `--sort`, `--limit`, `--page`, and `--completed-first`/`--pending-first`, for the status
+    /// listing
+    listing: ListOptions,
+
+    /// Things to warn about once logging is set up, like deprecated flags
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+enum Command {
+    /// Print the campaign's progress and milestones
+    #[default]
+    Status,
+
+    /// `suggest [--count N]`: Suggest amounts for new milestones
+    Suggest { count: usize },
+
+    /// `--format fact-sheet`: Print the campaign's [`FactSheet`] JSON, for widgets
+    ///
+    /// [`FactSheet`]: relay_st_jude::FactSheet
+    FactSheet,
+
+    /// `--json-canonical`: Print the campaign as stable JSON, for diffing and archiving
+    CanonicalJson,
+
+    /// `project --over 48h --rate R [--boost HH:MM-HH:MM=F]...`: Project when milestones will be
+    /// reached
+    Project {
+        horizon: Duration,
+
+        /// Dollars per hour. We only ever see one sample, so there's no history to derive it from.
+        rate: Option<f64>,
+        boosts: Vec<Boost>,
+    },
+
+    /// `--selftest`: Check the tool works, without touching the network
+    SelfTest,
+}
+
+/// A daily window, in UTC, when donations come in `factor` times faster
+#[derive(Debug, PartialEq)]
+struct Boost {
+    /// Minutes after midnight
+    start: u64,
+    end: u64,
+    factor: f64,
+}
+
+impl std::str::FromStr for Boost {
+    type Err = Report;
+
+    /// Parse `HH:MM-HH:MM=F`, like `18:00-19:00=2.0`
+    fn from_str(s: &str) -> Result<Self, Report> {
+        let invalid = || Report::msg(format!("Invalid boost {:?}, expected HH:MM-HH:MM=F", s));
+        let minutes = |hm: &str| -> Result<u64, Report> {
+            let (h, m) = hm.split_once(':').ok_or_else(invalid)?;
+            match (h.parse::<u64>(), m.parse::<u64>()) {
+                (Ok(h), Ok(m)) if h < 24 && m < 60 => Ok(h * 60 + m),
+                _ => Err(invalid()),
+            }
+        };
+
+        let (window, factor) = s.split_once('=').ok_or_else(invalid)?;
+        let (start, end) = window.split_once('-').ok_or_else(invalid)?;
+
+        Ok(Self {
+            start: minutes(start)?,
+            end: minutes(end)?,
+            factor: factor.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Report> {
+        let mut parsed = Self::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-q" | "--quiet" => parsed.verbosity = -1,
+                "-v" | "--verbose" => parsed.verbosity += 1,
+                "-vv" => parsed.verbosity += 2,
+                "--selftest" => parsed.command = Command::SelfTest,
+                "--json-canonical" => parsed.command = Command::CanonicalJson,
+                "--format" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| Report::msg("--format needs a format"))?;
+                    parsed.command = match value.as_str() {
+                        "text" => Command::Status,
+                        "fact-sheet" => Command::FactSheet,
+                        _ => {
+                            return Err(Report::msg(format!(
+                                "Unknown --format {:?}, expected \"text\" or \"fact-sheet\"",
+                                value
+                            )))
+                        }
+                    };
+                }
+                "suggest" if parsed.command == Command::Status => {
+                    parsed.command = Command::Suggest { count: 3 };
+                }
+                "--count" => match &mut parsed.command {
+                    Command::Suggest { count } => *count = parse_value(&mut args, "--count")?,
+                    _ => return Err(Report::msg("--count only works with `suggest`")),
+                },
+                "project" if parsed.command == Command::Status => {
+                    parsed.command = Command::Project {
+                        horizon: Duration::from_secs(48 * 60 * 60),
+                        rate: None,
+                        boosts: vec![],
+                    };
+                }
+                "--over" | "--hours" | "--rate" | "--boost" => match &mut parsed.command {
+                    Command::Project {
+                        horizon,
+                        rate,
+                        boosts,
+                    } => match arg.as_str() {
+                        "--over" => *horizon = parse_value::<HumanDuration>(&mut args, "--over")?.0,
+                        "--hours" => {
+                            let hours: u64 = parse_value(&mut args, "--hours")?;
+                            let secs = hours.checked_mul(60 * 60).ok_or_else(|| {
+                                Report::msg(format!(
+                                    "Invalid --hours {:?}: too many hours",
+                                    hours.to_string()
+                                ))
+                            })?;
+                            *horizon = Duration::from_secs(secs);
+                            parsed.warnings.push(format!(
+                                "--hours is deprecated, use --over {}",
+                                HumanDuration(*horizon)
+                            ));
+                        }
+                        "--rate" => *rate = Some(parse_value(&mut args, "--rate")?),
+                        _ => boosts.push(parse_value(&mut args, "--boost")?),
+                    },
+                    _ => return Err(Report::msg(format!("{} only works with `project`", arg))),
+                },
+                "--sort" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| Report::msg("--sort needs an order"))?;
+                    parsed.listing.sort = match value.as_str() {
+                        "amount" => MilestoneSort::Amount,
+                        "remaining" => MilestoneSort::Remaining,
+                        // We only ever see the current total, not when each milestone was hit
+                        "recent" => {
+                            return Err(Report::msg(
+                                "--sort recent needs donation history, which isn't available",
+                            ))
+                        }
+                        _ => {
+                            return Err(Report::msg(format!(
+                                "Unknown --sort {:?}, expected \"amount\" or \"remaining\"",
+                                value
+                            )))
+                        }
+                    };
+                }
+                "--limit" => {
+                    parsed.listing.limit = match parse_value(&mut args, "--limit")? {
+                        0 => None,
+                        limit => Some(limit),
+                    };
+                }
+                "--page" => match parse_value::<usize>(&mut args, "--page")? {
+                    0 => return Err(Report::msg("--page counts from 1")),
+                    page => parsed.listing.page = page - 1,
+                },
+                "--completed-first" => parsed.listing.completed = Some(CompletedPlacement::First),
+                "--pending-first" => parsed.listing.completed = Some(CompletedPlacement::Last),
+                _ => return Err(Report::msg(format!("Unknown argument: {}", arg))),
+            }
+        }
+
+        if parsed.command != Command::Status && parsed.listing != ListOptions::default() {
+            return Err(Report::msg(
+                "--sort, --limit, --page, --completed-first, and --pending-first only work with \
+                 the status listing",
+            ));
+        }
+
+        // Check these now, rather than after waiting on the network
+        if let Command::Project { rate: None, .. } = parsed.command {
+            return Err(Report::msg("`project` needs a --rate, in dollars per hour"));
+        }
+        if let Command::Project { horizon, .. } = parsed.command {
+            // Boosts become one window per day, and each window is checked every few minutes
+            if horizon > MAX_HORIZON {
+                return Err(Report::msg(format!(
+                    "Can't project more than {} ahead, got {}",
+                    HumanDuration(MAX_HORIZON),
+                    HumanDuration(horizon)
+                )));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// How much to log to stderr
+    ///
+    /// - `-q`: errors only
+    /// - default: warnings
+    /// - `-v`: info, like request timing
+    /// - `-vv`: debug, like request and response sizes
+    fn log_level(&self) -> LevelFilter {
+        match self.verbosity {
+            i8::MIN..=-1 => LevelFilter::ERROR,
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::INFO,
+            2 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Parse the value following `flag`
+fn parse_value<T>(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<T, Report>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = args
+        .next()
+        .ok_or_else(|| Report::msg(format!("{} needs a value", flag)))?;
+    value
+        .parse()
+        .map_err(|e| Report::msg(format!("Invalid {} {:?}: {}", flag, value, e)))
 }
 
 fn main() -> Result<(), Report> {
-    setup()?;
+    let args = Args::parse(std::env::args().skip(1))?;
+    setup(&args)?;
+    for warning in &args.warnings {
+        tracing::warn!("{}", warning);
+    }
 
-    let mut campaign: Campaign = Campaign::fetch()?;
+    if args.command == Command::SelfTest {
+        return self_test();
+    }
+
+    // Don't download text we won't print
+    let output = match args.command {
+        Command::FactSheet => OutputKind::FactSheet,
+        _ => OutputKind::Status,
+    };
+    let campaign = Campaign::fetch_by_selecting(
+        relay::VANITY,
+        relay::SLUG,
+        CampaignQuery::for_output(output),
+    )?;
+    let fetched_at = SystemTime::now();
+
+    match args.command {
+        Command::Status => print_status(&campaign, &args.listing),
+        Command::FactSheet => {
+            let sheet = campaign
+                .fact_sheet()
+                .source(relay::CAMPAIGN_URL, fetched_at);
+            println!("{}", sheet.to_json());
+        }
+        Command::CanonicalJson => print!("{}", campaign.to_canonical_json()),
+        Command::Suggest { count } => print_suggestions(&campaign, count),
+        Command::Project {
+            horizon,
+            rate: Some(rate),
+            boosts,
+        } => print_projection(&campaign, fetched_at, horizon, rate, &boosts)?,
+        Command::Project { rate: None, .. } => unreachable!("Args::parse requires a --rate"),
+        Command::SelfTest => unreachable!(),
+    }
 
-    // Sort milestones by $$
-    campaign
-        .milestones
-        .sort_by_key(|milestone| (milestone.amount.usd() * 100.) as u64);
+    Ok(())
+}
+
+fn self_test() -> Result<(), Report> {
+    println!("relay-st-jude {} self-test", env!("CARGO_PKG_VERSION"));
 
+    let results = relay_st_jude::selftest::run();
+    for result in &results {
+        let status = if result.passed() { "ok  " } else { "FAIL" };
+        println!("    {} {:20} {:>8.2?}", status, result.area, result.elapsed);
+        if let Err(e) = &result.outcome {
+            println!("         {}", e);
+        }
+    }
+
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    if failed == 0 {
+        println!("All {} checks passed", results.len());
+        Ok(())
+    } else {
+        Err(Report::msg(format!(
+            "{} of {} self-test checks failed",
+            failed,
+            results.len()
+        )))
+    }
+}
+
+fn print_status(campaign: &Campaign, listing: &ListOptions) {
+    println!("{}", campaign.summary(listing));
+
+    let more = campaign.milestone_page(listing).more;
+    if more > 0 && campaign.status != Some(CampaignStatus::Unpublished) {
+        println!("    … and {} more (use --limit 0 for all)", more);
+    }
+}
+
+fn print_suggestions(campaign: &Campaign, count: usize) {
     println!("{}!", campaign.name);
     println!("{} of {}", campaign.total_amount_raised, campaign.goal);
 
-    for milestone in &campaign.milestones {
-        // Indent
-        print!("    ");
-
-        if milestone.amount < campaign.total_amount_raised {
-            // Print an indicator if we've made it
-            print!("✅ ");
-            // don't print % after we pass it
-            print!("     ");
-        } else {
-            // ... or an indicator if we have not
-            print!("🤞 ");
-            // print percent for not-yet-completed milestones
-            print!(
-                "{}",
-                percent(campaign.total_amount_raised, milestone.amount)
-            );
+    let suggestions = campaign.suggest_milestones(count);
+    if suggestions.is_empty() {
+        println!("No room for new milestones above the current total");
+        return;
+    }
+
+    println!("Suggested milestones:");
+    for suggestion in suggestions {
+        println!(
+            "    {:15} - between {} and {}",
+            suggestion.amount, suggestion.gap.start, suggestion.gap.end
+        );
+    }
+}
+
+fn print_projection(
+    campaign: &Campaign,
+    now: SystemTime,
+    horizon: Duration,
+    rate: f64,
+    boosts: &[Boost],
+) -> Result<(), Report> {
+    const MINUTE: u64 = 60;
+    const DAY: u64 = 24 * 60 * MINUTE;
+
+    // Repeat each boost every day, starting with today's, until the horizon
+    let midnight = now - Duration::from_secs(now.duration_since(UNIX_EPOCH)?.as_secs() % DAY);
+    let mut windows = vec![];
+    for day in 0..=(horizon.as_secs() / DAY + 1) {
+        for boost in boosts {
+            let start = boost.start * MINUTE;
+            let end = if boost.end > boost.start {
+                boost.end * MINUTE
+            } else {
+                // Overnight, like 23:00-06:00
+                boost.end * MINUTE + DAY
+            };
+            windows.push(RateWindow {
+                start: midnight + Duration::from_secs(day * DAY + start),
+                end: midnight + Duration::from_secs(day * DAY + end),
+                factor: boost.factor,
+            });
         }
-        // Padding after the above
-        print!(" ");
+    }
+
+    let assumptions = Assumptions {
+        rate_per_hour: Some(Usd::new(rate)),
+        windows,
+        ..Assumptions::new(horizon)
+    };
+    let history = [Sample {
+        at: now,
+        total: campaign.total_amount_raised,
+    }];
+    let projection = simulate::project(campaign, &history, &assumptions)?;
+
+    println!("{}!", campaign.name);
+    println!(
+        "{} of {}, at {} an hour",
+        campaign.total_amount_raised, campaign.goal, projection.rate_per_hour
+    );
+    println!("Projected over the next {}:", HumanDuration(horizon));
 
-        println!("{:15} - {}", milestone.amount, milestone.description);
+    for crossing in &projection.crossings {
+        let when = match (crossing.after, crossing.earliest, crossing.latest) {
+            (Some(after), Some(earliest), Some(latest)) => format!(
+                "in {} ({} to {})",
+                hours_minutes(after),
+                hours_minutes(earliest),
+                hours_minutes(latest)
+            ),
+            (Some(after), Some(earliest), None) => format!(
+                "in {} ({} or later)",
+                hours_minutes(after),
+                hours_minutes(earliest)
+            ),
+            (None, Some(earliest), _) => {
+                format!("maybe, in {} at the soonest", hours_minutes(earliest))
+            }
+            _ => "not in time".to_string(),
+        };
+        println!(
+            "    {:15} - {} - {}",
+            crossing.amount, when, crossing.description
+        );
     }
 
     Ok(())
 }
 
-fn setup() -> Result<(), Report> {
+fn hours_minutes(d: Duration) -> String {
+    let minutes = d.as_secs() / 60;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+fn setup(args: &Args) -> Result<(), Report> {
     if std::env::var("RUST_LIB_BACKTRACE").is_err() {
         std::env::set_var("RUST_LIB_BACKTRACE", "1")
     }
     color_eyre::install()?;
 
+    // RUST_LOG wins over the flags, but we only understand a bare level like "debug"
+    let rust_log = std::env::var("RUST_LOG").ok();
+    let level = match rust_log.as_deref().map(str::parse::<LevelFilter>) {
+        Some(Ok(level)) => level,
+        _ => args.log_level(),
+    };
+
+    // Logs go to stderr so that stdout stays parseable
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .init();
+
+    if let Some(rust_log) = rust_log {
+        if rust_log.parse::<LevelFilter>().is_err() {
+            tracing::warn!(
+                "Ignoring RUST_LOG={:?}, expected a level like \"debug\"",
+                rust_log
+            );
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,682 @@
+//! Every GraphQL operation we send to Tiltify, in one place
+//!
+//! Each operation is a type implementing [`Operation`], and is listed in [`REGISTRY`] along with
+//! a saved response for it. Adding a new API call means implementing the trait and adding a
+//! fixture; the tests below notice if either is missing, and pin the exact query text of every
+//! registered operation. `--selftest` parses every fixture.
+
+use crate::{Campaign, CampaignId, Report};
+
+use serde_json::{json, Value};
+
+use std::convert::TryFrom;
+
+/// Which of a campaign's fields to ask for
+///
+/// Fields that aren't asked for parse as empty, so every selection still deserializes into the
+/// same [`Campaign`]. Pick one with [`CampaignQuery::for_output`] to avoid downloading text that
+/// won't be shown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CampaignQuery {
+    /// Everything
+    #[default]
+    Full,
+
+    /// Everything but the campaign's description, which is the bulk of a response
+    NoDescription,
+
+    /// The campaign's id, name, slug, and status, its totals, and milestone amounts without their
+    /// names
+    Amounts,
+
+    /// Everything in [`Full`](CampaignQuery::Full), plus the campaign's live stream, cause,
+    /// schedule, and rewards
+    ///
+    /// These fields haven't been checked against a live response yet, so they're only asked for
+    /// when you opt in.
+    Details,
+}
+
+/// What a fetched campaign is going to be used for
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OutputKind {
+    /// A full status listing, description and all
+    Status,
+
+    /// A [`FactSheet`](crate::FactSheet), which shows milestone names but not the description
+    FactSheet,
+
+    /// Just numbers, like for a metrics exporter
+    Metrics,
+}
+
+impl CampaignQuery {
+    /// The smallest query with everything `output` needs
+    pub fn for_output(output: OutputKind) -> Self {
+        match output {
+            OutputKind::Status => CampaignQuery::Full,
+            OutputKind::FactSheet => CampaignQuery::NoDescription,
+            OutputKind::Metrics => CampaignQuery::Amounts,
+        }
+    }
+
+    /// The GraphQL selection set for this query
+    ///
+    /// The strict mirror types in `strict.rs` need to be kept in sync with the details selection,
+    /// which has every field.
+    fn selection(self) -> String {
+        const AMOUNT: &str = "{\n        currency\n        value\n    }";
+        const AVATAR: &str = "{\n        alt\n        height\n        width\n        src\n    }";
+
+        let mut fields = vec!["id", "name", "slug"];
+        if matches!(self, CampaignQuery::Full | CampaignQuery::Details) {
+            fields.push("description");
+        }
+        fields.push("status");
+
+        let mut selection = String::from("{\n");
+        for field in fields {
+            selection.push_str(&format!("    {}\n", field));
+        }
+        if self != CampaignQuery::Amounts {
+            selection.push_str(&format!("    avatar {}\n", AVATAR));
+            selection.push_str(&format!(
+                "    team {{\n        name\n        avatar {}\n    }}\n",
+                AVATAR.replace("\n", "\n    ")
+            ));
+        }
+        if self == CampaignQuery::Details {
+            selection.push_str("    livestream {\n        type\n        channel\n    }\n");
+            selection.push_str("    cause {\n        name\n        slug\n    }\n");
+        }
+        selection.push_str(&format!("    totalAmountRaised {}\n", AMOUNT));
+        selection.push_str(&format!("    goal {}\n", AMOUNT));
+        selection.push_str(&format!("    originalGoal {}\n", AMOUNT));
+        if self == CampaignQuery::Details {
+            selection.push_str("    publishedAt\n    endsAt\n");
+        }
+        selection.push_str("    milestones {\n        id\n");
+        if self != CampaignQuery::Amounts {
+            selection.push_str("        name\n");
+        }
+        selection.push_str(&format!(
+            "        amount {}\n",
+            AMOUNT.replace("\n", "\n    ")
+        ));
+        selection.push_str("    }\n");
+        if self == CampaignQuery::Details {
+            selection.push_str(&format!(
+                "    rewards {{\n        name\n        description\n        amount {}\n        \
+                 quantity\n        remaining\n        active\n    }}\n",
+                AMOUNT.replace("\n", "\n    ")
+            ));
+        }
+        selection.push('}');
+
+        selection
+    }
+}
+
+/// One GraphQL operation: its query text, its variables, and how to read its response
+pub(crate) trait Operation {
+    /// What a successful response parses into
+    type Output;
+
+    /// The GraphQL operation name, which must be unique across the registry
+    fn name() -> &'static str;
+
+    /// The full query document
+    fn query() -> String;
+
+    /// This instance's variables
+    fn variables(&self) -> Value;
+
+    /// Parse a whole response, `data` and `errors` included
+    fn parse(response: Value) -> Result<Self::Output, Report>;
+
+    /// The JSON request to send
+    fn body(&self) -> Value {
+        json!({
+            "operationName": Self::name(),
+            "variables": self.variables(),
+            "query": Self::query(),
+        })
+    }
+}
+
+/// Look up a campaign by its owner's vanity url and its slug
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CampaignBySlug<'a> {
+    pub(crate) vanity: &'a str,
+    pub(crate) slug: &'a str,
+}
+
+impl CampaignBySlug<'_> {
+    /// The query text for an operation called `name`, asking for `selection`
+    fn query_selecting(name: &str, selection: CampaignQuery) -> String {
+        format!(
+            "query {}($vanity: String, $slug: String) {{\n\
+             campaign(vanity: $vanity, slug: $slug) {}\n\
+             }}",
+            name,
+            selection.selection()
+        )
+    }
+}
+
+impl Operation for CampaignBySlug<'_> {
+    type Output = Campaign;
+
+    fn name() -> &'static str {
+        "get_campaign_by_vanity_and_slug"
+    }
+
+    fn query() -> String {
+        CampaignBySlug::query_selecting(Self::name(), CampaignQuery::Full)
+    }
+
+    fn variables(&self) -> Value {
+        json!({
+            "vanity": self.vanity,
+            "slug": self.slug,
+        })
+    }
+
+    fn parse(response: Value) -> Result<Campaign, Report> {
+        Campaign::try_from(response)
+    }
+}
+
+/// [`CampaignBySlug`], without the description
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CampaignSummaryBySlug<'a>(pub(crate) CampaignBySlug<'a>);
+
+impl Operation for CampaignSummaryBySlug<'_> {
+    type Output = Campaign;
+
+    fn name() -> &'static str {
+        "get_campaign_summary_by_vanity_and_slug"
+    }
+
+    fn query() -> String {
+        CampaignBySlug::query_selecting(Self::name(), CampaignQuery::NoDescription)
+    }
+
+    fn variables(&self) -> Value {
+        self.0.variables()
+    }
+
+    fn parse(response: Value) -> Result<Campaign, Report> {
+        Campaign::try_from(response)
+    }
+}
+
+/// [`CampaignBySlug`], with only the numbers
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CampaignAmountsBySlug<'a>(pub(crate) CampaignBySlug<'a>);
+
+impl Operation for CampaignAmountsBySlug<'_> {
+    type Output = Campaign;
+
+    fn name() -> &'static str {
+        "get_campaign_amounts_by_vanity_and_slug"
+    }
+
+    fn query() -> String {
+        CampaignBySlug::query_selecting(Self::name(), CampaignQuery::Amounts)
+    }
+
+    fn variables(&self) -> Value {
+        self.0.variables()
+    }
+
+    fn parse(response: Value) -> Result<Campaign, Report> {
+        Campaign::try_from(response)
+    }
+}
+
+/// [`CampaignBySlug`], with the fields we haven't seen in a live response yet
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CampaignDetailsBySlug<'a>(pub(crate) CampaignBySlug<'a>);
+
+impl Operation for CampaignDetailsBySlug<'_> {
+    type Output = Campaign;
+
+    fn name() -> &'static str {
+        "get_campaign_details_by_vanity_and_slug"
+    }
+
+    fn query() -> String {
+        CampaignBySlug::query_selecting(Self::name(), CampaignQuery::Details)
+    }
+
+    fn variables(&self) -> Value {
+        self.0.variables()
+    }
+
+    fn parse(response: Value) -> Result<Campaign, Report> {
+        Campaign::try_from(response)
+    }
+}
+
+/// Look up a campaign by its [`CampaignId`]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CampaignById<'a> {
+    pub(crate) id: &'a CampaignId,
+}
+
+impl Operation for CampaignById<'_> {
+    type Output = Campaign;
+
+    fn name() -> &'static str {
+        "get_campaign_by_id"
+    }
+
+    fn query() -> String {
+        format!(
+            "query get_campaign_by_id($id: ID!) {{\n\
+             campaign(id: $id) {}\n\
+             }}",
+            CampaignQuery::Full.selection()
+        )
+    }
+
+    fn variables(&self) -> Value {
+        json!({
+            "id": self.id.as_str(),
+        })
+    }
+
+    fn parse(response: Value) -> Result<Campaign, Report> {
+        Campaign::try_from(response)
+    }
+}
+
+/// An [`Operation`] as listed in the [`REGISTRY`]
+pub(crate) struct Registered {
+    pub(crate) name: fn() -> &'static str,
+    pub(crate) query: fn() -> String,
+
+    /// A saved response to this operation
+    pub(crate) fixture: &'static str,
+
+    /// Parse a response, throwing away the output
+    pub(crate) check: fn(Value) -> Result<(), Report>,
+}
+
+const fn register<O: Operation>(fixture: &'static str) -> Registered {
+    Registered {
+        name: O::name,
+        query: O::query,
+        fixture,
+        check: check::<O>,
+    }
+}
+
+fn check<O: Operation>(response: Value) -> Result<(), Report> {
+    O::parse(response).map(drop)
+}
+
+/// Every operation this crate can send
+pub(crate) const REGISTRY: &[Registered] = &[
+    register::<CampaignBySlug>(include_str!("example-response.json")),
+    register::<CampaignSummaryBySlug>(include_str!("example-response-summary.json")),
+    register::<CampaignAmountsBySlug>(include_str!("example-response-totals.json")),
+    register::<CampaignDetailsBySlug>(include_str!("example-response-livestream.json")),
+    register::<CampaignById>(include_str!("example-response.json")),
+];
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    use std::collections::BTreeSet;
+
+    /// Every `impl Operation for` in this file must be registered
+    #[test]
+    fn registry_is_complete() {
+        let impls = include_str!("ops.rs")
+            .lines()
+            .filter(|line| line.starts_with("impl Operation for "))
+            .count();
+        assert_eq!(
+            impls,
+            REGISTRY.len(),
+            "an Operation is missing from REGISTRY"
+        );
+
+        let names: BTreeSet<_> = REGISTRY.iter().map(|op| (op.name)()).collect();
+        assert_eq!(
+            names.len(),
+            REGISTRY.len(),
+            "operation names must be unique"
+        );
+    }
+
+    /// The exact text we send. Changing a query is fine, but it should be on purpose.
+    #[test]
+    fn query_snapshots() {
+        let snapshots: Vec<_> = REGISTRY
+            .iter()
+            .map(|op| format!("# {}\n{}\n", (op.name)(), (op.query)()))
+            .collect();
+
+        assert_eq!(
+            snapshots.join("\n"),
+            indoc::indoc!(
+                r#"
+                # get_campaign_by_vanity_and_slug
+                query get_campaign_by_vanity_and_slug($vanity: String, $slug: String) {
+                campaign(vanity: $vanity, slug: $slug) {
+                    id
+                    name
+                    slug
+                    description
+                    status
+                    avatar {
+                        alt
+                        height
+                        width
+                        src
+                    }
+                    team {
+                        name
+                        avatar {
+                            alt
+                            height
+                            width
+                            src
+                        }
+                    }
+                    totalAmountRaised {
+                        currency
+                        value
+                    }
+                    goal {
+                        currency
+                        value
+                    }
+                    originalGoal {
+                        currency
+                        value
+                    }
+                    milestones {
+                        id
+                        name
+                        amount {
+                            currency
+                            value
+                        }
+                    }
+                }
+                }
+
+                # get_campaign_summary_by_vanity_and_slug
+                query get_campaign_summary_by_vanity_and_slug($vanity: String, $slug: String) {
+                campaign(vanity: $vanity, slug: $slug) {
+                    id
+                    name
+                    slug
+                    status
+                    avatar {
+                        alt
+                        height
+                        width
+                        src
+                    }
+                    team {
+                        name
+                        avatar {
+                            alt
+                            height
+                            width
+                            src
+                        }
+                    }
+                    totalAmountRaised {
+                        currency
+                        value
+                    }
+                    goal {
+                        currency
+                        value
+                    }
+                    originalGoal {
+                        currency
+                        value
+                    }
+                    milestones {
+                        id
+                        name
+                        amount {
+                            currency
+                            value
+                        }
+                    }
+                }
+                }
+
+                # get_campaign_amounts_by_vanity_and_slug
+                query get_campaign_amounts_by_vanity_and_slug($vanity: String, $slug: String) {
+                campaign(vanity: $vanity, slug: $slug) {
+                    id
+                    name
+                    slug
+                    status
+                    totalAmountRaised {
+                        currency
+                        value
+                    }
+                    goal {
+                        currency
+                        value
+                    }
+                    originalGoal {
+                        currency
+                        value
+                    }
+                    milestones {
+                        id
+                        amount {
+                            currency
+                            value
+                        }
+                    }
+                }
+                }
+
+                # get_campaign_details_by_vanity_and_slug
+                query get_campaign_details_by_vanity_and_slug($vanity: String, $slug: String) {
+                campaign(vanity: $vanity, slug: $slug) {
+                    id
+                    name
+                    slug
+                    description
+                    status
+                    avatar {
+                        alt
+                        height
+                        width
+                        src
+                    }
+                    team {
+                        name
+                        avatar {
+                            alt
+                            height
+                            width
+                            src
+                        }
+                    }
+                    livestream {
+                        type
+                        channel
+                    }
+                    cause {
+                        name
+                        slug
+                    }
+                    totalAmountRaised {
+                        currency
+                        value
+                    }
+                    goal {
+                        currency
+                        value
+                    }
+                    originalGoal {
+                        currency
+                        value
+                    }
+                    publishedAt
+                    endsAt
+                    milestones {
+                        id
+                        name
+                        amount {
+                            currency
+                            value
+                        }
+                    }
+                    rewards {
+                        name
+                        description
+                        amount {
+                            currency
+                            value
+                        }
+                        quantity
+                        remaining
+                        active
+                    }
+                }
+                }
+
+                # get_campaign_by_id
+                query get_campaign_by_id($id: ID!) {
+                campaign(id: $id) {
+                    id
+                    name
+                    slug
+                    description
+                    status
+                    avatar {
+                        alt
+                        height
+                        width
+                        src
+                    }
+                    team {
+                        name
+                        avatar {
+                            alt
+                            height
+                            width
+                            src
+                        }
+                    }
+                    totalAmountRaised {
+                        currency
+                        value
+                    }
+                    goal {
+                        currency
+                        value
+                    }
+                    originalGoal {
+                        currency
+                        value
+                    }
+                    milestones {
+                        id
+                        name
+                        amount {
+                            currency
+                            value
+                        }
+                    }
+                }
+                }
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn query_for_output() {
+        assert_eq!(
+            CampaignQuery::for_output(OutputKind::Status),
+            CampaignQuery::Full
+        );
+        assert_eq!(
+            CampaignQuery::for_output(OutputKind::FactSheet),
+            CampaignQuery::NoDescription
+        );
+        assert_eq!(
+            CampaignQuery::for_output(OutputKind::Metrics),
+            CampaignQuery::Amounts
+        );
+    }
+
+    /// Fields that haven't been checked against a live response are only in the details query
+    #[test]
+    fn details_are_opt_in() {
+        const DETAILS: &[&str] = &["livestream", "cause", "publishedAt", "endsAt", "rewards"];
+
+        for query in [
+            CampaignQuery::Full,
+            CampaignQuery::NoDescription,
+            CampaignQuery::Amounts,
+        ] {
+            let selection = query.selection();
+            for field in DETAILS {
+                assert!(!selection.contains(field), "{:?} has {}", query, field);
+            }
+        }
+
+        let selection = CampaignQuery::Details.selection();
+        for field in DETAILS {
+            assert!(selection.contains(field), "details are missing {}", field);
+        }
+    }
+
+    /// Smaller selections still parse, leaving out exactly what they didn't ask for
+    #[test]
+    fn partial_selections_parse() {
+        let full =
+            CampaignBySlug::parse(serde_json::from_str(REGISTRY[0].fixture).unwrap()).unwrap();
+        let summary =
+            CampaignSummaryBySlug::parse(serde_json::from_str(REGISTRY[1].fixture).unwrap())
+                .unwrap();
+        let amounts =
+            CampaignAmountsBySlug::parse(serde_json::from_str(REGISTRY[2].fixture).unwrap())
+                .unwrap();
+
+        assert!(summary.description.is_empty());
+        assert_eq!(summary.milestones, full.milestones);
+        assert_eq!(summary.total_amount_raised, full.total_amount_raised);
+
+        assert!(amounts.milestones.iter().all(|m| m.description.is_empty()));
+        let amounts_of = |c: &Campaign| c.milestones.iter().map(|m| m.amount).collect::<Vec<_>>();
+        assert_eq!(amounts_of(&amounts), amounts_of(&full));
+        assert_eq!(amounts.goal, full.goal);
+    }
+
+    #[test]
+    fn bodies() {
+        let id = CampaignId::new("121745");
+        let by_id = CampaignById { id: &id }.body();
+        assert_eq!(by_id["operationName"], "get_campaign_by_id");
+        assert_eq!(by_id["variables"], json!({ "id": "121745" }));
+
+        let by_slug = CampaignBySlug {
+            vanity: "@relay-fm",
+            slug: "relay-st-jude-21",
+        }
+        .body();
+        assert_eq!(
+            by_slug["variables"],
+            json!({ "vanity": "@relay-fm", "slug": "relay-st-jude-21" })
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Frames for animating browser-source overlays, like OBS
+//!
+//! An overlay wants more than the latest numbers: to animate a change it needs to know where the
+//! total came from, and whether a milestone was just completed. Each [`OverlayFrame`] carries
+//! both, plus a sequence number so the overlay can tell when it missed an update.
+//!
+//! ```rust
+//! use relay_st_jude::{overlay, Campaign};
+//!
+//! # fn poll() -> Campaign { unimplemented!() }
+//! # fn send(_: String) {}
+//! # fn run() {
+//! let mut previous: Option<Campaign> = None;
+//! for seq in 0.. {
+//!     let next = poll();
+//!     let frame = overlay::to_overlay_frame(previous.as_ref(), &next, seq);
+//!     send(frame.to_sse());
+//!     previous = Some(next);
+//! }
+//! # }
+//! ```
+
+use crate::completion::{CompletionRules, Context};
+use crate::{Campaign, Milestone};
+
+use serde::Serialize;
+
+/// A comment line for server-sent event streams, to keep idle connections open
+pub const SSE_KEEP_ALIVE: &str = ": keep-alive\n\n";
+
+/// One update for an overlay to animate
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct OverlayFrame {
+    /// Increases by one for every frame, so gaps mean missed updates
+    pub seq: u64,
+
+    /// The total before this update, in USD
+    ///
+    /// For the first frame, this is the same as `total_usd`.
+    pub previous_total_usd: f64,
+
+    /// The total after this update, in USD
+    pub total_usd: f64,
+
+    /// The campaign goal, in USD
+    pub goal_usd: f64,
+
+    /// The milestone completed by this update, if any
+    ///
+    /// If one update completes several milestones, this is the largest of them.
+    pub milestone_completed: Option<CompletedMilestone>,
+}
+
+/// A milestone that was completed, as shown in an [`OverlayFrame`]
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct CompletedMilestone {
+    /// What the hosts will do now that it's reached
+    pub description: String,
+
+    /// The milestone's amount, in USD
+    ///
+    /// Milestones without an amount are never completed, but convert to `0.0`.
+    pub amount_usd: f64,
+}
+
+impl From<&Milestone> for CompletedMilestone {
+    fn from(milestone: &Milestone) -> Self {
+        Self {
+            description: milestone.description.clone(),
+            amount_usd: milestone.amount.map_or(0., |a| a.usd()),
+        }
+    }
+}
+
+/// Build the frame for an update from `prev` to `next`
+///
+/// Pass `None` for `prev` on the first poll. Nothing counts as completed then, since we don't
+/// know what changed.
+pub fn to_overlay_frame(prev: Option<&Campaign>, next: &Campaign, seq: u64) -> OverlayFrame {
+    let total = next.total_amount_raised;
+    let previous_total = prev.map_or(total, |p| p.total_amount_raised);
+
+    // A milestone completes when the total moves from below it to at or above it
+    let milestone_completed = prev
+        .map(|prev| {
+            CompletionRules::new().newly_completed(&Context::new(prev), &Context::new(next))
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .max_by_key(|m| m.amount)
+        .map(CompletedMilestone::from);
+
+    OverlayFrame {
+        seq,
+        previous_total_usd: previous_total.usd(),
+        total_usd: total.usd(),
+        goal_usd: next.goal.usd(),
+        milestone_completed,
+    }
+}
+
+impl OverlayFrame {
+    /// Compact JSON, for polling endpoints
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("OverlayFrame is always serializable")
+    }
+
+    /// A complete server-sent event for this frame
+    ///
+    /// The event id is the sequence number, so a reconnecting `EventSource` reports the last frame
+    /// it saw in `Last-Event-ID`.
+    pub fn to_sse(&self) -> String {
+        format!(
+            "id: {}\nevent: frame\ndata: {}\n\n",
+            self.seq,
+            self.to_json()
+        )
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn campaign(raised: f64) -> Campaign {
+        Campaign::builder()
+            .name("Test")
+            .raised(raised)
+            .goal(1_000.)
+            .milestone("Half way", 500.)
+            .milestone("A start", 100.)
+            .milestone("A quarter", 250.)
+            .unpriced_milestone("Not priced yet")
+            .build()
+    }
+
+    #[test]
+    fn first_frame_completes_nothing() {
+        let frame = to_overlay_frame(None, &campaign(300.), 0);
+
+        assert_eq!(frame.previous_total_usd, 300.);
+        assert_eq!(frame.total_usd, 300.);
+        assert_eq!(frame.milestone_completed, None);
+    }
+
+    #[test]
+    fn milestone_completed_hint() {
+        // Landing exactly on a milestone completes it
+        let frame = to_overlay_frame(Some(&campaign(50.)), &campaign(100.), 1);
+        assert_eq!(frame.milestone_completed.unwrap().description, "A start");
+
+        // ... but starting on it doesn't complete it again
+        let frame = to_overlay_frame(Some(&campaign(100.)), &campaign(200.), 2);
+        assert_eq!(frame.milestone_completed, None);
+
+        // A big jump reports the largest milestone it passed
+        let frame = to_overlay_frame(Some(&campaign(200.)), &campaign(600.), 3);
+        assert_eq!(
+            frame.milestone_completed,
+            Some(CompletedMilestone {
+                description: "Half way".to_string(),
+                amount_usd: 500.,
+            })
+        );
+
+        // Refunds don't complete anything
+        let frame = to_overlay_frame(Some(&campaign(600.)), &campaign(90.), 4);
+        assert_eq!(frame.milestone_completed, None);
+    }
+
+    #[test]
+    fn json_shape() {
+        let frame = to_overlay_frame(Some(&campaign(50.)), &campaign(100.5), 7);
+
+        assert_eq!(
+            frame.to_json(),
+            r#"{"seq":7,"previous_total_usd":50.0,"total_usd":100.5,"goal_usd":1000.0,"milestone_completed":{"description":"A start","amount_usd":100.0}}"#
+        );
+    }
+
+    #[test]
+    fn sse_framing() {
+        let frames: Vec<String> = (10..13)
+            .map(|seq| to_overlay_frame(None, &campaign(1.), seq).to_sse())
+            .collect();
+
+        for (frame, seq) in frames.iter().zip(10..) {
+            let lines: Vec<&str> = frame.split('\n').collect();
+            assert_eq!(lines[0], format!("id: {}", seq));
+            assert_eq!(lines[1], "event: frame");
+            assert!(lines[2].starts_with("data: {"));
+
+            // Events end with exactly one blank line, and the data never contains a newline
+            assert_eq!(&lines[3..], ["", ""]);
+        }
+
+        assert!(SSE_KEEP_ALIVE.starts_with(':'));
+        assert!(SSE_KEEP_ALIVE.ends_with("\n\n"));
+    }
+}
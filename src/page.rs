@@ -0,0 +1,143 @@
+use crate::Campaign;
+
+use std::fmt::Write;
+
+/// Where a campaign lives on Tiltify: a vanity like `@relay-fm` and a slug like
+/// `relay-st-jude-21`
+///
+/// The API only sends back the slug, as [`Campaign::slug`], never the vanity. So a [`Campaign`]
+/// only has a page when it was fetched by vanity and slug. See [`Campaign::page`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CampaignPage {
+    /// The team or user's vanity, with or without its leading `@`
+    pub vanity: String,
+
+    /// The campaign's slug
+    pub slug: String,
+}
+
+impl CampaignPage {
+    /// The page for `vanity` and `slug`, as they'd be passed to [`Campaign::fetch_by`]
+    pub fn new(vanity: impl Into<String>, slug: impl Into<String>) -> Self {
+        Self {
+            vanity: vanity.into(),
+            slug: slug.into(),
+        }
+    }
+
+    /// The campaign's public page, like `https://tiltify.com/@relay-fm/relay-st-jude-21`
+    ///
+    /// The vanity always gets exactly one leading `@`, and anything that isn't safe in a URL path
+    /// is percent-encoded.
+    pub fn url(&self) -> String {
+        let vanity = self.vanity.strip_prefix('@').unwrap_or(&self.vanity);
+        format!(
+            "https://tiltify.com/@{}/{}",
+            encode(vanity),
+            encode(&self.slug)
+        )
+    }
+
+    /// Where to send people to donate
+    pub fn donate_url(&self) -> String {
+        format!("{}/donate", self.url())
+    }
+}
+
+impl Campaign {
+    /// The campaign's public page, if it was fetched by vanity and slug
+    ///
+    /// See [`CampaignPage::url`].
+    pub fn url(&self) -> Option<String> {
+        self.page.as_ref().map(CampaignPage::url)
+    }
+
+    /// Where to send people to donate, if the campaign was fetched by vanity and slug
+    pub fn donate_url(&self) -> Option<String> {
+        self.page.as_ref().map(CampaignPage::donate_url)
+    }
+
+    /// Where to watch the campaign's [`livestream`](Campaign::livestream), if it has one
+    ///
+    /// Twitch channels become `https://twitch.tv/{channel}`. We don't know how to link to other
+    /// services, so for those this is the channel as the API sent it. Fetch with
+    /// [`CampaignQuery::Details`](crate::CampaignQuery::Details) to get the live stream at all.
+    pub fn livestream_url(&self) -> Option<String> {
+        let livestream = self.livestream.as_ref()?;
+        if livestream.kind.eq_ignore_ascii_case("twitch") {
+            Some(format!("https://twitch.tv/{}", encode(&livestream.channel)))
+        } else {
+            Some(livestream.channel.clone())
+        }
+    }
+}
+
+/// Percent-encode everything but RFC 3986's unreserved characters
+fn encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => write!(encoded, "%{:02X}", byte).expect("writing to a String can't fail"),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+    use crate::relay;
+
+    #[test]
+    fn relay() {
+        let page = CampaignPage::new(relay::VANITY, relay::SLUG);
+        assert_eq!(page.url(), relay::CAMPAIGN_URL);
+        assert_eq!(
+            page.donate_url(),
+            "https://tiltify.com/@relay-fm/relay-st-jude-21/donate"
+        );
+
+        // The @ is optional
+        assert_eq!(
+            CampaignPage::new("relay-fm", relay::SLUG).url(),
+            relay::CAMPAIGN_URL
+        );
+    }
+
+    #[test]
+    fn encodes() {
+        let page = CampaignPage::new("@@café crew", "a/b?c#d");
+        assert_eq!(
+            page.url(),
+            "https://tiltify.com/@%40caf%C3%A9%20crew/a%2Fb%3Fc%23d"
+        );
+    }
+
+    /// Only campaigns fetched by vanity and slug know their page
+    #[test]
+    fn campaigns() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.url(), None);
+
+        campaign.page = Some(CampaignPage::new(relay::VANITY, relay::SLUG));
+        assert_eq!(campaign.url().as_deref(), Some(relay::CAMPAIGN_URL));
+    }
+
+    #[test]
+    fn stream_links() {
+        let url = |json| Campaign::from_json(json).unwrap().livestream_url();
+
+        assert_eq!(
+            url(include_str!("example-response-livestream.json")).as_deref(),
+            Some("https://twitch.tv/relayfm")
+        );
+        assert_eq!(
+            url(include_str!("example-response-livestream-youtube.json")).as_deref(),
+            Some("UCThisIsNotARealChannel")
+        );
+        assert_eq!(url(include_str!("example-response.json")), None);
+    }
+}
@@ -0,0 +1,18 @@
+//! The types most programs using this crate need, in one import
+//!
+//! ```rust
+//! use relay_st_jude::prelude::*;
+//!
+//! fn describe(campaign: &Campaign) -> String {
+//!     format!("{} of {}", campaign.total_amount_raised, campaign.goal)
+//! }
+//! ```
+//!
+//! Everything here is also available from the crate root. Adding or removing names here is a
+//! breaking change, since it can clash with names in your own modules.
+
+pub use crate::overlay::OverlayFrame;
+pub use crate::{
+    Campaign, CampaignId, CampaignStatus, FactSheet, Milestone, MilestoneGap, MilestoneSuggestion,
+    Report, RoundingPolicy, Usd,
+};
@@ -0,0 +1,460 @@
+use crate::{text, Campaign, Milestone, Usd};
+
+use std::fmt;
+
+/// A percentage, like how much of the goal has been raised
+///
+/// The wrapped number is in percentage points, so `Percent(6.8)` is 6.8%. It isn't capped: a
+/// campaign that's blown past its goal is at `112.3%`, and displays that way.
+///
+/// It displays with one decimal by default. A precision or width in the format spec applies to
+/// the whole thing, so `{:.2}` gives `6.80%` and `{:6}` gives `  6.8%`. Fill and alignment work
+/// as usual.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+pub struct Percent(pub f64);
+
+impl Percent {
+    /// `part` as a percentage of `whole`, or 0% when `whole` is zero
+    pub fn of(part: Usd, whole: Usd) -> Self {
+        if whole == Usd::ZERO {
+            Self(0.)
+        } else {
+            Self(100. * part.usd() / whole.usd())
+        }
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = format!("{:.*}%", f.precision().unwrap_or(1), self.0);
+        text::pad_formatter(f, &s, text::Align::Right)
+    }
+}
+
+impl Campaign {
+    /// How much of the goal has been raised so far
+    ///
+    /// This is 0% if the goal is $0, rather than NaN.
+    pub fn percent_raised(&self) -> Percent {
+        Percent::of(self.total_amount_raised, self.goal)
+    }
+
+    /// How much of the goal has been raised so far, where 1.0 is all of it
+    ///
+    /// Like [`percent_raised`](Campaign::percent_raised), this is 0.0 if the goal is $0. It goes
+    /// past 1.0 once the campaign passes its goal.
+    pub fn fraction_raised(&self) -> f64 {
+        self.percent_raised().0 / 100.
+    }
+
+    /// The cheapest milestone that hasn't been reached yet
+    ///
+    /// A milestone is reached once the total is at least its amount, so one exactly at the total
+    /// doesn't count as next. Milestones without an amount yet are skipped, and so is the order
+    /// the API listed them in. Of milestones with the same amount, the first listed wins.
+    pub fn next_milestone(&self) -> Option<&Milestone> {
+        self.milestones
+            .iter()
+            .filter(|m| {
+                m.amount
+                    .is_some_and(|amount| amount > self.total_amount_raised)
+            })
+            .min_by_key(|m| m.amount)
+    }
+
+    /// The most expensive milestone that's been reached
+    ///
+    /// This counts a milestone exactly at the total as reached, the same as
+    /// [`Milestone::is_completed`]. Of milestones with the same amount, the first listed wins.
+    pub fn last_reached_milestone(&self) -> Option<&Milestone> {
+        self.milestones
+            .iter()
+            .filter(|m| m.is_completed(self.total_amount_raised))
+            .rev()
+            .max_by_key(|m| m.amount)
+    }
+
+    /// The milestones that have been reached, cheapest first
+    ///
+    /// This uses the same rule as [`last_reached_milestone`](Campaign::last_reached_milestone), so
+    /// a milestone exactly at the total is reached. Milestones without an amount yet are in
+    /// neither this list nor [`milestones_upcoming`](Campaign::milestones_upcoming). Milestones
+    /// with the same amount stay in the order the API listed them.
+    pub fn milestones_reached(&self) -> Vec<&Milestone> {
+        self.milestones_where(|amount| amount <= self.total_amount_raised)
+    }
+
+    /// The milestones that haven't been reached yet, cheapest first
+    ///
+    /// The first of these is [`next_milestone`](Campaign::next_milestone).
+    pub fn milestones_upcoming(&self) -> Vec<&Milestone> {
+        self.milestones_where(|amount| amount > self.total_amount_raised)
+    }
+
+    /// The milestones a total going from `from` to `to` crosses, cheapest first
+    ///
+    /// That's milestones with an amount above `from` and at most `to`, matching
+    /// [`Milestone::is_completed`]: a milestone exactly at `from` was already reached, and one
+    /// exactly at `to` has just been. Calling this with each pair of consecutive totals reports
+    /// every milestone once. Nothing is crossed when `to` isn't above `from`, including when the
+    /// total goes down.
+    pub fn milestones_crossed_between(&self, from: Usd, to: Usd) -> Vec<&Milestone> {
+        self.milestones_where(|amount| from < amount && amount <= to)
+    }
+
+    fn milestones_where(&self, keep: impl Fn(Usd) -> bool) -> Vec<&Milestone> {
+        let mut milestones: Vec<_> = self
+            .milestones
+            .iter()
+            .filter(|m| m.amount.is_some_and(&keep))
+            .collect();
+        milestones.sort_by_key(|m| m.amount);
+        milestones
+    }
+
+    /// Every milestone, in the API's order, with its [`progress`](Milestone::progress)
+    pub fn milestone_progress(&self) -> Vec<(&Milestone, f64)> {
+        self.milestones
+            .iter()
+            .map(|m| (m, m.progress(self.total_amount_raised)))
+            .collect()
+    }
+
+    /// Whether the total has reached the current goal
+    pub fn is_goal_reached(&self) -> bool {
+        self.total_amount_raised >= self.goal
+    }
+
+    /// Whether the total has reached the goal the campaign started with
+    ///
+    /// Campaigns without an [`original_goal`](Campaign::original_goal) never changed their goal,
+    /// so this is the same as [`is_goal_reached`](Campaign::is_goal_reached) for them.
+    pub fn is_original_goal_reached(&self) -> bool {
+        self.total_amount_raised >= self.original_goal.unwrap_or(self.goal)
+    }
+
+    /// How much the goal has gone up since the campaign started, or $0 if it hasn't
+    ///
+    /// The API only has the original and current goals, not how many times it changed in between.
+    pub fn goal_increase(&self) -> Usd {
+        match self.original_goal {
+            Some(original) => self.goal.saturating_sub(original),
+            None => Usd::ZERO,
+        }
+    }
+
+    /// A text progress bar `width` cells long, like `███░░░░░░░ 38%`
+    ///
+    /// This is [`text::progress_bar`] of [`fraction_raised`](Campaign::fraction_raised). Use a
+    /// [`text::ProgressBar`] to pick other characters or drop the percentage.
+    pub fn render_progress_bar(&self, width: usize) -> String {
+        text::progress_bar(self.fraction_raised(), width)
+    }
+
+    /// How much is left to raise to reach the goal, or $0 once it's reached
+    pub fn amount_remaining(&self) -> Usd {
+        self.goal.saturating_sub(self.total_amount_raised)
+    }
+
+    /// How far past the goal the campaign is, if it's passed it
+    ///
+    /// This is `None` when the total is exactly the goal: that's reached, but not over.
+    pub fn amount_over_goal(&self) -> Option<Usd> {
+        if self.total_amount_raised > self.goal {
+            Some(self.total_amount_raised - self.goal)
+        } else {
+            None
+        }
+    }
+}
+
+impl Milestone {
+    /// How far `raised` is towards this milestone
+    ///
+    /// This is 0% if the milestone's amount is $0 or hasn't been set yet, rather than NaN.
+    pub fn percent_of(&self, raised: Usd) -> Percent {
+        Percent::of(raised, self.amount.unwrap_or(Usd::ZERO))
+    }
+
+    /// How far `raised` is towards this milestone, from 0.0 to 1.0
+    ///
+    /// This is exactly 1.0 once the milestone [is completed](Milestone::is_completed), including
+    /// a $0 milestone, and 0.0 for a milestone without an amount yet. Unlike
+    /// [`percent_of`](Milestone::percent_of), it doesn't go past the end.
+    pub fn progress(&self, raised: Usd) -> f64 {
+        match self.amount {
+            None => 0.,
+            Some(_) if self.is_completed(raised) => 1.,
+            Some(amount) => (raised.cents() as f64 / amount.cents() as f64).max(0.),
+        }
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    #[test]
+    fn displays() {
+        assert_eq!(Percent(6.8).to_string(), "6.8%");
+        assert_eq!(Percent(6.8).to_string(), format!("{:.1}", Percent(6.8)));
+        assert_eq!(format!("{:.2}", Percent(6.8)), "6.80%");
+        assert_eq!(format!("{:.0}", Percent(41.2)), "41%");
+        assert_eq!(format!("{:>6}", Percent(6.8)), "  6.8%");
+        assert_eq!(Percent(112.345).to_string(), "112.3%");
+    }
+
+    #[test]
+    fn campaign_and_milestones() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let raised = campaign.total_amount_raised;
+
+        assert_eq!(campaign.percent_raised().to_string(), "6.8%");
+        assert!((campaign.fraction_raised() - 0.067_990_2).abs() < 1e-6);
+        let percents: Vec<_> = campaign
+            .milestones
+            .iter()
+            .map(|m| m.percent_of(raised).to_string())
+            .collect();
+        assert_eq!(percents, ["30.2%", "41.2%", "113.3%", "11.6%"]);
+    }
+
+    #[test]
+    fn remaining_and_over() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.amount_remaining(), Usd::new(310_669.93));
+        assert_eq!(campaign.amount_over_goal(), None);
+
+        // Exactly at the goal, even when the amounts came from floats that don't add up exactly
+        campaign.goal = Usd::new(0.3);
+        campaign.total_amount_raised = Usd::new(0.1) + Usd::new(0.2);
+        assert_eq!(campaign.amount_remaining(), Usd::ZERO);
+        assert_eq!(campaign.amount_over_goal(), None);
+
+        campaign.total_amount_raised += Usd::from_cents(1);
+        assert_eq!(campaign.amount_remaining(), Usd::ZERO);
+        assert_eq!(campaign.amount_over_goal(), Some(Usd::new(0.01)));
+
+        campaign.total_amount_raised -= Usd::from_cents(2);
+        assert_eq!(campaign.amount_remaining(), Usd::new(0.01));
+        assert_eq!(campaign.amount_over_goal(), None);
+    }
+
+    #[test]
+    fn next_and_last_reached() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        fn description(m: Option<&Milestone>) -> Option<&str> {
+            m.map(|m| m.description.as_str())
+        }
+
+        assert_eq!(
+            description(campaign.next_milestone()),
+            Some("Stephen dissembles his NeXTCube on stream")
+        );
+        assert_eq!(
+            description(campaign.last_reached_milestone()),
+            Some("Myke and Stephen attempt Flight Simulator again")
+        );
+
+        // Exactly at a milestone's amount counts as reaching it
+        campaign.total_amount_raised = Usd::new(55_000.);
+        assert_eq!(
+            description(campaign.next_milestone()),
+            Some("Stephen & Myke go to space via KSP")
+        );
+        assert_eq!(
+            description(campaign.last_reached_milestone()),
+            Some("Stephen dissembles his NeXTCube on stream")
+        );
+        campaign.total_amount_raised -= Usd::from_cents(1);
+        assert_eq!(
+            description(campaign.next_milestone()),
+            Some("Stephen dissembles his NeXTCube on stream")
+        );
+
+        // Nothing reached, then everything
+        campaign.total_amount_raised = Usd::ZERO;
+        assert_eq!(description(campaign.last_reached_milestone()), None);
+        campaign.total_amount_raised = Usd::new(1_000_000.);
+        assert_eq!(description(campaign.next_milestone()), None);
+        assert_eq!(
+            description(campaign.last_reached_milestone()),
+            Some("$1 million raised in 3 years!")
+        );
+
+        // Ties go to the first listed, either way
+        campaign.milestones[1].amount = Some(Usd::new(196_060.44));
+        assert_eq!(
+            description(campaign.last_reached_milestone()),
+            Some("Stephen dissembles his NeXTCube on stream")
+        );
+        campaign.total_amount_raised = Usd::new(100_000.);
+        assert_eq!(
+            description(campaign.next_milestone()),
+            Some("Stephen dissembles his NeXTCube on stream")
+        );
+    }
+
+    #[test]
+    fn reached_and_upcoming() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        fn amounts(milestones: Vec<&Milestone>) -> Vec<String> {
+            milestones
+                .iter()
+                .map(|m| m.amount.unwrap().to_string())
+                .collect()
+        }
+
+        // $22,663.40 raised, with the API listing milestones out of order
+        assert_eq!(amounts(campaign.milestones_reached()), ["$20,000.00"]);
+        assert_eq!(
+            amounts(campaign.milestones_upcoming()),
+            ["$55,000.00", "$75,000.00", "$196,060.44"]
+        );
+        assert_eq!(
+            campaign.milestones_upcoming().first().copied(),
+            campaign.next_milestone()
+        );
+
+        // Exactly at an amount is reached, same as next_milestone
+        campaign.total_amount_raised = Usd::new(55_000.);
+        assert_eq!(
+            amounts(campaign.milestones_reached()),
+            ["$20,000.00", "$55,000.00"]
+        );
+        assert_eq!(
+            campaign.milestones_upcoming().first().copied(),
+            campaign.next_milestone()
+        );
+
+        // Unpriced milestones are in neither
+        let campaign =
+            Campaign::from_json(include_str!("example-response-null-amount.json")).unwrap();
+        assert_eq!(
+            campaign.milestones_reached().len() + campaign.milestones_upcoming().len(),
+            campaign.milestones.len() - 1
+        );
+    }
+
+    #[test]
+    fn crossed_between() {
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let crossed = |from: f64, to: f64| -> Vec<i64> {
+            campaign
+                .milestones_crossed_between(Usd::new(from), Usd::new(to))
+                .iter()
+                .map(|m| m.amount.unwrap().cents() / 100)
+                .collect()
+        };
+
+        assert_eq!(crossed(0., 100_000.), [20_000, 55_000, 75_000]);
+        assert_eq!(crossed(20_000., 54_999.99), [] as [i64; 0]);
+        // At `to` is crossed, at `from` was crossed last time
+        assert_eq!(crossed(19_999.99, 20_000.), [20_000]);
+        assert_eq!(crossed(20_000., 20_000.01), [] as [i64; 0]);
+
+        // Each milestone is reported exactly once across a run of polls
+        let totals = [0., 20_000., 54_000., 75_000., 75_000., 300_000.];
+        let all: Vec<_> = totals
+            .windows(2)
+            .flat_map(|w| crossed(w[0], w[1]))
+            .collect();
+        assert_eq!(all, [20_000, 55_000, 75_000, 196_060]);
+
+        // Backwards, or not moving, crosses nothing
+        assert_eq!(crossed(100_000., 0.), [] as [i64; 0]);
+        assert_eq!(crossed(55_000., 55_000.), [] as [i64; 0]);
+    }
+
+    #[test]
+    fn milestone_progress() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        let progress: Vec<_> = campaign
+            .milestone_progress()
+            .into_iter()
+            .map(|(m, p)| (m.amount.unwrap().cents(), (p * 1000.).round() / 1000.))
+            .collect();
+        assert_eq!(
+            progress,
+            [
+                (7_500_000, 0.302),
+                (5_500_000, 0.412),
+                (2_000_000, 1.),
+                (19_606_044, 0.116)
+            ]
+        );
+
+        // Exactly 1.0 at the milestone, even from floats that don't add up exactly
+        let milestone = Milestone {
+            id: None,
+            description: "Thirty cents".to_string(),
+            amount: Some(Usd::new(0.3)),
+        };
+        assert_eq!(milestone.progress(Usd::new(0.1) + Usd::new(0.2)), 1.);
+        assert!(milestone.progress(Usd::new(0.29)) < 1.);
+        assert_eq!(milestone.progress(Usd::new(-5.)), 0.);
+
+        // $0 milestones are done, unpriced ones haven't started
+        campaign.milestones[0].amount = Some(Usd::ZERO);
+        campaign.milestones[1].amount = None;
+        let progress = campaign.milestone_progress();
+        assert_eq!(progress[0].1, 1.);
+        assert_eq!(progress[1].1, 0.);
+    }
+
+    #[test]
+    fn goals() {
+        // The goal went from $100 to $333,333.33
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.original_goal, Some(Usd::new(100.)));
+        assert!(!campaign.is_goal_reached());
+        assert!(campaign.is_original_goal_reached());
+        assert_eq!(campaign.goal_increase(), Usd::new(333_233.33));
+
+        campaign.total_amount_raised = campaign.goal;
+        assert!(campaign.is_goal_reached());
+
+        // A lowered goal isn't an increase
+        campaign.original_goal = Some(Usd::new(400_000.));
+        assert!(!campaign.is_original_goal_reached());
+        assert_eq!(campaign.goal_increase(), Usd::ZERO);
+
+        // The API sends null for a goal that never changed
+        let campaign =
+            Campaign::from_json(include_str!("example-response-null-amount.json")).unwrap();
+        assert_eq!(campaign.original_goal, None);
+        assert_eq!(campaign.goal_increase(), Usd::ZERO);
+        assert_eq!(
+            campaign.is_original_goal_reached(),
+            campaign.is_goal_reached()
+        );
+    }
+
+    #[test]
+    fn progress_bar() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.render_progress_bar(20), "█░░░░░░░░░░░░░░░░░░░ 7%");
+
+        campaign.goal = Usd::ZERO;
+        assert_eq!(campaign.render_progress_bar(4), "░░░░ 0%");
+    }
+
+    #[test]
+    fn zero_is_not_nan() {
+        let mut campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        campaign.goal = Usd::ZERO;
+        assert_eq!(campaign.percent_raised(), Percent(0.));
+        assert_eq!(campaign.fraction_raised(), 0.);
+
+        campaign.goal = Usd::new(10_000.);
+        assert!((campaign.fraction_raised() - 2.266_34).abs() < 1e-9);
+        assert_eq!(campaign.percent_raised().to_string(), "226.6%");
+
+        let secret = Milestone {
+            id: None,
+            description: "Something secret".to_string(),
+            amount: None,
+        };
+        assert_eq!(secret.percent_of(Usd::new(100.)), Percent(0.));
+    }
+}
@@ -0,0 +1,164 @@
+//! Building the GraphQL requests we send to Tiltify
+//!
+//! Polling hits the same handful of queries over and over, so the serialized request bodies are
+//! cached and reused instead of being rebuilt on every request.
+
+use crate::ops::Operation;
+
+use std::sync::{Arc, Mutex};
+
+/// A fully serialized GraphQL request, ready to send
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RequestBody(Vec<u8>);
+
+/// How many bodies to keep around. Most programs only ever ask for one.
+const CACHE_CAPACITY: usize = 8;
+
+static CACHE: Mutex<BodyCache> = Mutex::new(BodyCache::new(CACHE_CAPACITY));
+
+impl RequestBody {
+    fn new(query: &serde_json::Value) -> Self {
+        Self(query.to_string().into_bytes())
+    }
+
+    /// The body for `op`, reused across calls with the same variables when possible
+    pub(crate) fn for_operation<O: Operation>(op: &O) -> Arc<Self> {
+        let key = format!("{} {}", O::name(), op.variables());
+        CACHE
+            .lock()
+            .unwrap()
+            .get_or_insert_with(&key, || Self::new(&op.body()))
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A tiny least-recently-used cache of request bodies, keyed by operation name and variables
+///
+/// The capacity is small enough that a linear scan beats anything fancier.
+#[derive(Debug)]
+struct BodyCache {
+    capacity: usize,
+    /// Least recently used first
+    entries: Vec<(String, Arc<RequestBody>)>,
+}
+
+impl BodyCache {
+    const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::new(),
+        }
+    }
+
+    fn get_or_insert_with(
+        &mut self,
+        key: &str,
+        build: impl FnOnce() -> RequestBody,
+    ) -> Arc<RequestBody> {
+        if let Some(i) = self.entries.iter().position(|(k, _)| k == key) {
+            // Move it to the back, it's now the most recently used
+            let entry = self.entries.remove(i);
+            let body = entry.1.clone();
+            self.entries.push(entry);
+
+            tracing::trace!(key, "reusing cached request body");
+            return body;
+        }
+
+        if self.entries.len() >= self.capacity {
+            self.entries.remove(0);
+        }
+
+        let body = Arc::new(build());
+        self.entries.push((key.to_string(), body.clone()));
+        body
+    }
+}
+
+/// Hooks for `benches/query.rs`, which can't see `RequestBody` itself. Not part of the API.
+#[doc(hidden)]
+pub mod bench {
+    use super::RequestBody;
+    use crate::ops::{CampaignBySlug, Operation};
+
+    /// Build the body for fetching a campaign from scratch, returning its length
+    pub fn fresh_body(vanity: &str, slug: &str) -> usize {
+        RequestBody::new(&CampaignBySlug { vanity, slug }.body())
+            .as_bytes()
+            .len()
+    }
+
+    /// Get the body for fetching a campaign through the cache, returning its length
+    pub fn cached_body(vanity: &str, slug: &str) -> usize {
+        RequestBody::for_operation(&CampaignBySlug { vanity, slug })
+            .as_bytes()
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    use crate::ops::{CampaignById, CampaignBySlug};
+    use crate::CampaignId;
+
+    const RELAY: CampaignBySlug = CampaignBySlug {
+        vanity: "@relay-fm",
+        slug: "relay-st-jude-21",
+    };
+
+    /// The cached body must be exactly what we'd have built from scratch
+    #[test]
+    fn cached_body_is_identical() {
+        let fresh = RequestBody::new(&RELAY.body());
+
+        let first = RequestBody::for_operation(&RELAY);
+        let second = RequestBody::for_operation(&RELAY);
+
+        assert_eq!(fresh.as_bytes(), first.as_bytes());
+        assert_eq!(fresh.as_bytes(), second.as_bytes());
+
+        let value: serde_json::Value = serde_json::from_slice(first.as_bytes()).unwrap();
+        assert_eq!(value, RELAY.body());
+    }
+
+    /// Different operations with the same-looking variables mustn't share a body
+    #[test]
+    fn cache_keys_include_the_operation() {
+        let id = CampaignId::new("121745");
+        let by_id = RequestBody::for_operation(&CampaignById { id: &id });
+        let by_slug = RequestBody::for_operation(&RELAY);
+
+        assert_ne!(by_id.as_bytes(), by_slug.as_bytes());
+    }
+
+    #[test]
+    fn cache_reuses_and_evicts() {
+        let mut cache = BodyCache::new(2);
+        let mut builds = 0;
+        let mut get = |cache: &mut BodyCache, slug: &str| {
+            cache.get_or_insert_with(slug, || {
+                builds += 1;
+                RequestBody::new(&CampaignBySlug { vanity: "@v", slug }.body())
+            })
+        };
+
+        let a = get(&mut cache, "a");
+        assert!(Arc::ptr_eq(&a, &get(&mut cache, "a")));
+
+        get(&mut cache, "b");
+        // "a" was used more recently than "b", so "b" goes first
+        get(&mut cache, "a");
+        get(&mut cache, "c");
+
+        let keys: Vec<&str> = cache.entries.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["a", "c"]);
+
+        get(&mut cache, "b");
+        assert_eq!(builds, 4);
+    }
+}
@@ -0,0 +1,242 @@
+//! The Relay FM for St. Jude campaign, which this crate was first written for
+//!
+//! This is the only place in the library that knows about Relay. Everything else takes a vanity
+//! and slug, or a [`CampaignId`](crate::CampaignId), so it works the same for any Tiltify
+//! campaign.
+
+use crate::{Campaign, CampaignPage, Report, Usd};
+
+/// The Relay FM team's vanity url on Tiltify
+pub const VANITY: &str = "@relay-fm";
+
+/// The current campaign's slug
+pub const SLUG: &str = KnownCampaign::latest().slug();
+
+/// The current campaign's public page
+pub const CAMPAIGN_URL: &str = "https://tiltify.com/@relay-fm/relay-st-jude-21";
+
+/// The Relay FM for St. Jude campaigns whose slugs we've confirmed against a real response
+///
+/// Other years get added once their slugs are confirmed, which isn't a breaking change. So match
+/// with a wildcard arm, or use [`KnownCampaign::ALL`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KnownCampaign {
+    /// 2021, the one this crate was first written for
+    StJude2021,
+}
+
+impl KnownCampaign {
+    /// Every campaign, oldest first
+    pub const ALL: &'static [KnownCampaign] = &[KnownCampaign::StJude2021];
+
+    /// The most recent campaign, which [`Campaign::fetch`] fetches
+    pub const fn latest() -> Self {
+        KnownCampaign::StJude2021
+    }
+
+    /// The September the campaign ran in
+    pub const fn year(self) -> u16 {
+        match self {
+            KnownCampaign::StJude2021 => 2021,
+        }
+    }
+
+    /// The vanity the campaign is under, which is always Relay FM's
+    pub const fn vanity(self) -> &'static str {
+        VANITY
+    }
+
+    /// The campaign's slug on Tiltify
+    pub const fn slug(self) -> &'static str {
+        match self {
+            KnownCampaign::StJude2021 => "relay-st-jude-21",
+        }
+    }
+
+    /// Where the campaign is on Tiltify
+    pub fn page(self) -> CampaignPage {
+        CampaignPage::new(self.vanity(), self.slug())
+    }
+}
+
+/// Every [`KnownCampaign`] that could be fetched, from [`Campaign::fetch_all_known`]
+///
+/// One year failing doesn't stop the others from being fetched, so check
+/// [`errors`](AllKnownCampaigns::errors) before quoting a grand total.
+#[derive(Debug)]
+pub struct AllKnownCampaigns {
+    /// The campaigns that were fetched, oldest first
+    pub campaigns: Vec<Campaign>,
+
+    /// The campaigns that couldn't be fetched, and why
+    pub errors: Vec<(KnownCampaign, Report)>,
+}
+
+impl AllKnownCampaigns {
+    /// Everything raised by the campaigns that were fetched, see [`aggregate_total`]
+    pub fn total(&self) -> Usd {
+        aggregate_total(&self.campaigns)
+    }
+
+    /// Whether every campaign was fetched
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Fetch each of `known` with `fetch`, collecting the failures instead of stopping at them
+pub(crate) fn fetch_all_with(
+    known: &[KnownCampaign],
+    mut fetch: impl FnMut(KnownCampaign) -> Result<Campaign, Report>,
+) -> AllKnownCampaigns {
+    let mut all = AllKnownCampaigns {
+        campaigns: vec![],
+        errors: vec![],
+    };
+
+    for &known in known {
+        match fetch(known) {
+            Ok(campaign) => all.campaigns.push(campaign),
+            Err(e) => {
+                tracing::warn!(campaign = ?known, "couldn't fetch campaign: {}", e);
+                all.errors.push((known, e));
+            }
+        }
+    }
+
+    all
+}
+
+/// The total raised across `campaigns`, like "$1 million raised in 3 years!"
+///
+/// This adds up exactly, in cents. A total too big for a [`Usd`] stops at [`Usd::MAX`] instead
+/// of overflowing, which is a sign that something's wrong with the campaigns.
+pub fn aggregate_total(campaigns: &[Campaign]) -> Usd {
+    campaigns.iter().map(|c| c.total_amount_raised).sum()
+}
+
+/// Fetch the current Relay FM for St. Jude campaign from online
+pub fn fetch_current() -> Result<Campaign, Report> {
+    Campaign::fetch_known(KnownCampaign::latest())
+}
+
+/// Fetch the current campaign, failing if the response has any fields we don't know
+///
+/// See [`Campaign::from_json_strict_schema`] for details.
+pub fn fetch_current_strict_schema() -> Result<Campaign, Report> {
+    Campaign::fetch_by_strict_schema(VANITY, SLUG)
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    #[test]
+    fn well_formed() {
+        for &known in KnownCampaign::ALL {
+            let vanity = known.vanity();
+            assert!(vanity.starts_with('@'), "{:?}: {}", known, vanity);
+            assert!(!vanity[1..].contains('@'), "{:?}: {}", known, vanity);
+
+            let slug = known.slug();
+            assert!(!slug.is_empty(), "{:?}", known);
+            assert!(
+                slug.bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'),
+                "{:?}: {}",
+                known,
+                slug
+            );
+            assert!(
+                !slug.starts_with('-') && !slug.ends_with('-') && !slug.contains("--"),
+                "{:?}: {}",
+                known,
+                slug
+            );
+
+            // Nothing in them needs escaping
+            assert_eq!(
+                known.page().url(),
+                format!("https://tiltify.com/{}/{}", vanity, slug)
+            );
+        }
+    }
+
+    #[test]
+    fn totals() {
+        let campaigns = [
+            Campaign::builder().raised(1_000.01).build(),
+            Campaign::from_json(include_str!("example-response.json")).unwrap(),
+        ];
+        assert_eq!(aggregate_total(&campaigns), Usd::from_cents(2_366_341));
+        assert_eq!(aggregate_total(&[]), Usd::ZERO);
+
+        // Cents that don't add up exactly as floats still do here
+        let dimes = [
+            Campaign::builder().raised(0.1).build(),
+            Campaign::builder().raised(0.2).build(),
+        ];
+        assert_eq!(aggregate_total(&dimes), Usd::from_cents(30));
+
+        let mut huge = Campaign::builder().build();
+        huge.total_amount_raised = Usd::MAX;
+        let overflowing = [huge.clone(), huge, Campaign::builder().raised(1.).build()];
+        assert_eq!(aggregate_total(&overflowing), Usd::MAX);
+    }
+
+    #[test]
+    fn partial_failures() {
+        let all = fetch_all_with(KnownCampaign::ALL, |_| {
+            Campaign::from_json(include_str!("example-response.json"))
+        });
+        assert!(all.is_complete());
+        assert_eq!(all.total(), Usd::new(22_663.40));
+
+        // There's only one known campaign so far, so ask for it twice and fail the first time
+        let mut attempts = 0;
+        let all = fetch_all_with(
+            &[KnownCampaign::StJude2021, KnownCampaign::StJude2021],
+            |_| {
+                attempts += 1;
+                match attempts {
+                    1 => Err(Report::msg("HTTP 404")),
+                    _ => Campaign::from_json(include_str!("example-response.json")),
+                }
+            },
+        );
+
+        assert!(!all.is_complete());
+        assert_eq!(all.errors.len(), 1);
+        assert_eq!(all.errors[0].0, KnownCampaign::StJude2021);
+        assert_eq!(all.errors[0].1.to_string(), "HTTP 404");
+
+        let names: Vec<_> = all.campaigns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Relay FM for St. Jude 2021"]);
+        assert_eq!(all.total(), Usd::new(22_663.40));
+
+        let all = fetch_all_with(KnownCampaign::ALL, |_| Err(Report::msg("offline")));
+        assert!(all.campaigns.is_empty());
+        assert_eq!(all.errors.len(), KnownCampaign::ALL.len());
+        assert_eq!(all.total(), Usd::ZERO);
+    }
+
+    #[test]
+    fn registry() {
+        let all = KnownCampaign::ALL;
+        assert_eq!(all.last(), Some(&KnownCampaign::latest()));
+
+        for pair in all.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert!(pair[0].year() < pair[1].year());
+            assert_ne!(pair[0].slug(), pair[1].slug());
+        }
+
+        assert_eq!(SLUG, KnownCampaign::latest().slug());
+        assert_eq!(
+            KnownCampaign::latest().page().url(),
+            CAMPAIGN_URL,
+            "CAMPAIGN_URL is out of date"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{Campaign, Usd};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Something donors get for giving enough, like a sticker for $25
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Reward {
+    /// Name of the reward
+    pub name: String,
+
+    /// What the reward is, and how to claim it
+    #[serde(default)]
+    pub description: String,
+
+    /// The smallest donation that gets the reward
+    pub amount: Usd,
+
+    /// How many were offered in total, or `None` if there's no limit
+    #[serde(default)]
+    pub quantity: Option<u32>,
+
+    /// How many are left, or `None` if there's no limit
+    #[serde(default)]
+    pub remaining: Option<u32>,
+
+    /// Whether organizers are offering the reward right now
+    ///
+    /// Responses without the flag, or with it set to `null`, are taken to be active.
+    #[serde(default = "active_by_default", deserialize_with = "deserialize_active")]
+    pub active: bool,
+}
+
+fn active_by_default() -> bool {
+    true
+}
+
+/// Parse an `active` flag that may be `null`
+fn deserialize_active<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<bool>::deserialize(deserializer)?.unwrap_or_else(active_by_default))
+}
+
+impl Reward {
+    /// Whether every one of a limited reward has been claimed
+    pub fn is_sold_out(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    /// Whether donating now could still get this reward: it's active and not sold out
+    pub fn is_available(&self) -> bool {
+        self.active && !self.is_sold_out()
+    }
+}
+
+impl Campaign {
+    /// The rewards that can still be claimed, in the order the API sent them
+    ///
+    /// Inactive and sold out rewards are left out. Rewards are only fetched with
+    /// [`CampaignQuery::Details`](crate::CampaignQuery::Details), so this is empty for campaigns
+    /// fetched any other way.
+    pub fn available_rewards(&self) -> Vec<&Reward> {
+        self.rewards.iter().filter(|r| r.is_available()).collect()
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn example() -> Campaign {
+        Campaign::from_json(include_str!("example-response-rewards.json")).unwrap()
+    }
+
+    #[test]
+    fn parses() {
+        let campaign = example();
+        assert_eq!(
+            campaign.rewards[0],
+            Reward {
+                name: "Sticker".to_string(),
+                description: "A Relay for St. Jude sticker, mailed to you".to_string(),
+                amount: Usd::new(25.),
+                quantity: Some(500),
+                remaining: Some(12),
+                active: true,
+            }
+        );
+        Campaign::from_json_strict_schema(include_str!("example-response-rewards.json")).unwrap();
+
+        // Older responses didn't ask for any
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert!(campaign.rewards.is_empty());
+        assert!(campaign.available_rewards().is_empty());
+    }
+
+    /// Anything not limited, or not sent, is left empty
+    #[test]
+    fn optional_fields() {
+        let campaign = example();
+
+        let unlimited = &campaign.rewards[2];
+        assert_eq!(unlimited.name, "Thank you on stream");
+        assert_eq!((unlimited.quantity, unlimited.remaining), (None, None));
+        assert!(!unlimited.is_sold_out());
+
+        // No description, quantity, remaining, or active flag at all
+        let bare = &campaign.rewards[4];
+        assert_eq!(bare.description, "");
+        assert_eq!((bare.quantity, bare.remaining), (None, None));
+        assert!(bare.active);
+        assert!(bare.is_available());
+
+        // An explicit null is the same as leaving it out
+        let null = &campaign.rewards[5];
+        assert_eq!(null.name, "A postcard");
+        assert!(null.active);
+    }
+
+    #[test]
+    fn available() {
+        let campaign = example();
+        let sold_out = &campaign.rewards[1];
+        assert!(sold_out.is_sold_out());
+        assert!(!sold_out.is_available());
+
+        let retired = &campaign.rewards[3];
+        assert!(!retired.active);
+        assert!(!retired.is_sold_out());
+        assert!(!retired.is_available());
+
+        let names: Vec<_> = campaign
+            .available_rewards()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Sticker",
+                "Thank you on stream",
+                "A high five",
+                "A postcard"
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Offline checks of the crate's core behavior, for `relay-st-jude --selftest`
+//!
+//! When something looks wrong, the first question is "is it my network or the tool?". These
+//! checks answer the second half without touching the network: they run the parsing, formatting,
+//! and analysis code against fixtures compiled into the binary. The crate's own tests run them
+//! too, so they can't quietly rot.
+
+use crate::{ops, overlay, text, Campaign, CampaignStatus, Usd};
+
+use std::time::{Duration, Instant};
+
+const EXAMPLE_RESPONSE: &str = include_str!("example-response.json");
+const UNPUBLISHED_RESPONSE: &str = include_str!("example-response-unpublished.json");
+
+/// The outcome of one self-test check
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckResult {
+    /// Which area of the crate this covers, like "parse" or "format"
+    pub area: &'static str,
+
+    /// `Err` describes what went wrong
+    pub outcome: Result<(), String>,
+
+    /// How long the check took
+    pub elapsed: Duration,
+}
+
+impl CheckResult {
+    /// Whether the check passed
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+type Check = fn() -> Result<(), String>;
+
+const CHECKS: &[(&str, Check)] = &[
+    ("parse", parse),
+    ("parse-unpublished", parse_unpublished),
+    ("strict-schema", strict_schema),
+    ("operations", operations),
+    ("format", format),
+    ("text-width", text_width),
+    ("overlay", overlay_frames),
+    ("suggest", suggest),
+];
+
+/// Run every check, in order
+pub fn run() -> Vec<CheckResult> {
+    CHECKS
+        .iter()
+        .map(|&(area, check)| {
+            let start = Instant::now();
+            let outcome = check();
+
+            CheckResult {
+                area,
+                outcome,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect()
+}
+
+/// Fail the check with `message` unless `cond` holds
+fn ensure(cond: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if cond {
+        Ok(())
+    } else {
+        Err(message())
+    }
+}
+
+fn ensure_eq<T: PartialEq + std::fmt::Debug>(
+    what: &str,
+    actual: T,
+    expected: T,
+) -> Result<(), String> {
+    ensure(actual == expected, || {
+        format!("{}: expected {:?}, got {:?}", what, expected, actual)
+    })
+}
+
+fn example() -> Result<Campaign, String> {
+    Campaign::from_json(EXAMPLE_RESPONSE).map_err(|e| format!("{}", e))
+}
+
+fn parse() -> Result<(), String> {
+    let campaign = example()?;
+
+    ensure_eq("name", campaign.name.as_str(), "Relay FM for St. Jude 2021")?;
+    ensure_eq("raised", campaign.total_amount_raised, Usd::new(22_663.40))?;
+    ensure_eq("goal", campaign.goal, Usd::new(333_333.33))?;
+    ensure_eq("milestones", campaign.milestones.len(), 4)?;
+    ensure_eq("status", campaign.status, Some(CampaignStatus::Published))
+}
+
+fn parse_unpublished() -> Result<(), String> {
+    let campaign = Campaign::from_json(UNPUBLISHED_RESPONSE).map_err(|e| format!("{}", e))?;
+
+    ensure_eq("status", campaign.status, Some(CampaignStatus::Unpublished))
+}
+
+fn strict_schema() -> Result<(), String> {
+    // The saved example is exactly what we ask for...
+    Campaign::from_json_strict_schema(EXAMPLE_RESPONSE).map_err(|e| format!("{}", e))?;
+
+    // ... so strict mode should only object once something else shows up
+    let drifted = EXAMPLE_RESPONSE.replacen(r#""campaign": {"#, r#""campaign": {"drift": 1,"#, 1);
+    match Campaign::from_json_strict_schema(&drifted) {
+        Ok(_) => Err("an extra field in the example response was not reported".to_string()),
+        Err(e) => ensure(e.to_string().contains("unknown field"), || {
+            format!("unexpected error: {}", e)
+        }),
+    }
+}
+
+/// Every GraphQL operation names itself, and can read its saved response
+fn operations() -> Result<(), String> {
+    for op in ops::REGISTRY {
+        let query = (op.query)();
+        ensure(
+            query.starts_with(&format!("query {}(", (op.name)())),
+            || format!("{}: query has the wrong name: {}", (op.name)(), query),
+        )?;
+
+        let fixture = serde_json::from_str(op.fixture)
+            .map_err(|e| format!("{} fixture: {}", (op.name)(), e))?;
+        (op.check)(fixture).map_err(|e| format!("{}: {}", (op.name)(), e))?;
+    }
+
+    Ok(())
+}
+
+fn format() -> Result<(), String> {
+    let campaign = example()?;
+
+    ensure_eq(
+        "raised",
+        campaign.total_amount_raised.to_string().as_str(),
+        "$22,663.40",
+    )?;
+    ensure_eq(
+        "padded goal",
+        format!("{:15}", campaign.goal).as_str(),
+        "    $333,333.33",
+    )?;
+    ensure_eq("carry", Usd::new(999.999).to_string().as_str(), "$1,000.00")
+}
+
+fn text_width() -> Result<(), String> {
+    ensure_eq("ascii", text::display_width("KSP"), 3)?;
+    ensure_eq("cjk", text::display_width("寄付"), 4)?;
+    ensure_eq("emoji", text::display_width("✅🤞"), 4)?;
+    ensure_eq("zwj", text::display_width("👩‍🚀"), 2)?;
+    ensure_eq("combining", text::display_width("e\u{301}"), 1)
+}
+
+fn overlay_frames() -> Result<(), String> {
+    let next = example()?;
+    let mut prev = next.clone();
+    prev.total_amount_raised = Usd::new(19_000.);
+
+    let frame = overlay::to_overlay_frame(Some(&prev), &next, 42);
+    let completed = frame
+        .milestone_completed
+        .as_ref()
+        .map(|m| m.description.as_str());
+    ensure_eq(
+        "completed",
+        completed,
+        Some("Myke and Stephen attempt Flight Simulator again"),
+    )?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(&frame.to_json()).map_err(|e| format!("frame json: {}", e))?;
+    ensure_eq("seq", json["seq"].as_u64(), Some(42))?;
+    ensure(frame.to_sse().starts_with("id: 42\n"), || {
+        format!("bad sse framing: {:?}", frame.to_sse())
+    })
+}
+
+fn suggest() -> Result<(), String> {
+    let campaign = example()?;
+    let suggestions = campaign.suggest_milestones(3);
+
+    ensure_eq("count", suggestions.len(), 3)?;
+    ensure(
+        suggestions
+            .iter()
+            .all(|s| s.amount > campaign.total_amount_raised),
+        || format!("suggestion below the total: {:?}", suggestions),
+    )
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    #[test]
+    fn every_check_passes() {
+        let results = run();
+        assert_eq!(results.len(), CHECKS.len());
+
+        for result in results {
+            assert!(result.passed(), "{}: {:?}", result.area, result.outcome);
+        }
+    }
+}
@@ -0,0 +1,445 @@
+//! "What if" projections of a campaign's total
+//!
+//! During planning segments the hosts ask things like "if we keep this pace, when do we hit
+//! $300k?" and "what if we get a $10k matching hour?". [`project`] answers those by stepping the
+//! total forward at a base rate, adjusted by [`RateWindow`]s and one-off [`Injection`]s, and
+//! reports when each milestone would be crossed.
+//!
+//! Everything here is plain arithmetic over the inputs, so the same inputs always give the same
+//! projection.
+//!
+//! ```rust
+//! use relay_st_jude::simulate::{project, Assumptions, Sample};
+//! use relay_st_jude::{Campaign, Usd};
+//!
+//! use std::time::{Duration, SystemTime};
+//!
+//! # fn campaign() -> Campaign { unimplemented!() }
+//! # fn run() {
+//! let campaign: Campaign = campaign();
+//! let now = SystemTime::now();
+//!
+//! let assumptions = Assumptions {
+//!     rate_per_hour: Some(Usd::new(2_500.)),
+//!     ..Assumptions::new(Duration::from_secs(48 * 3600))
+//! };
+//! let history = [Sample { at: now, total: campaign.total_amount_raised }];
+//!
+//! for crossing in project(&campaign, &history, &assumptions).unwrap().crossings {
+//!     println!("{} after {:?}", crossing.description, crossing.after);
+//! }
+//! # }
+//! ```
+
+use crate::{Campaign, Report, Usd};
+
+use std::time::{Duration, SystemTime};
+
+const HOUR: f64 = 3600.;
+
+/// The campaign's total at some point in time
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sample {
+    /// When the total was seen
+    pub at: SystemTime,
+
+    /// The total raised at that time
+    pub total: Usd,
+}
+
+/// A stretch of time when donations come in faster or slower than usual
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RateWindow {
+    /// When the window opens
+    pub start: SystemTime,
+
+    /// When the window closes
+    pub end: SystemTime,
+
+    /// How much to multiply the base rate by, like `2.0` for a matching hour or `0.3` overnight
+    ///
+    /// Overlapping windows multiply together.
+    pub factor: f64,
+}
+
+/// A one-off donation, like a pledged corporate match
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Injection {
+    /// When the money arrives
+    pub at: SystemTime,
+
+    /// How much arrives
+    pub amount: Usd,
+}
+
+/// What to assume about the future
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assumptions {
+    /// Dollars raised per hour, or `None` to derive it from the history
+    pub rate_per_hour: Option<Usd>,
+
+    /// Adjustments to the base rate
+    pub windows: Vec<RateWindow>,
+
+    /// One-off amounts
+    pub injections: Vec<Injection>,
+
+    /// How far past the last sample to project
+    pub horizon: Duration,
+
+    /// The time between projected points
+    ///
+    /// Rate windows are checked at the start of each step, so use a step that divides them.
+    pub step: Duration,
+
+    /// How uncertain the growth is, as a fraction: `0.2` gives a ±20% band around the projection
+    pub uncertainty: f64,
+}
+
+impl Assumptions {
+    /// Project `horizon` ahead at the historical pace, in 5 minute steps, with a ±20% band
+    pub fn new(horizon: Duration) -> Self {
+        Self {
+            rate_per_hour: None,
+            windows: vec![],
+            injections: vec![],
+            horizon,
+            step: Duration::from_secs(5 * 60),
+            uncertainty: 0.2,
+        }
+    }
+}
+
+/// One point of a [`Projection`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProjectedPoint {
+    /// How long after the last sample this is
+    pub after: Duration,
+
+    /// The projected total
+    pub total: Usd,
+
+    /// The low end of the uncertainty band
+    pub low: Usd,
+
+    /// The high end of the uncertainty band
+    pub high: Usd,
+}
+
+/// When a milestone (or the goal) would be reached in a [`Projection`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crossing {
+    /// The milestone's description, or "Goal"
+    pub description: String,
+
+    /// The amount being crossed
+    pub amount: Usd,
+
+    /// How long after the last sample the projection crosses it, if it does within the horizon
+    pub after: Option<Duration>,
+
+    /// The soonest it could be crossed, going by the high end of the band
+    pub earliest: Option<Duration>,
+
+    /// The latest it could be crossed, going by the low end of the band
+    pub latest: Option<Duration>,
+}
+
+/// The result of [`project`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Projection {
+    /// The base rate used, in dollars per hour
+    pub rate_per_hour: Usd,
+
+    /// The projected totals, starting with the last sample
+    pub points: Vec<ProjectedPoint>,
+
+    /// Every milestone and the goal not yet reached, cheapest first
+    pub crossings: Vec<Crossing>,
+}
+
+/// Project `campaign` forward from the last of `history`
+///
+/// `history` must have at least one sample, and at least two spanning some time unless
+/// `assumptions` sets the rate. Samples can be in any order.
+pub fn project(
+    campaign: &Campaign,
+    history: &[Sample],
+    assumptions: &Assumptions,
+) -> Result<Projection, Report> {
+    let mut history = history.to_vec();
+    history.sort_by_key(|s| s.at);
+
+    let last = *history
+        .last()
+        .ok_or_else(|| Report::msg("Can't project without at least one sample"))?;
+
+    let rate = match assumptions.rate_per_hour {
+        Some(rate) => rate.usd(),
+        None => historical_rate(&history)?,
+    };
+    if assumptions.step == Duration::ZERO {
+        return Err(Report::msg("The projection step must be longer than zero"));
+    }
+
+    let start = last.total.usd();
+    // Totals add up in dollars and become whole cents at each point, so rounding doesn't
+    // accumulate across many small steps
+    let band = |total: f64, sign: f64| {
+        Usd::new(start + (total - start) * (1. + sign * assumptions.uncertainty))
+    };
+
+    let mut points = vec![ProjectedPoint {
+        after: Duration::ZERO,
+        total: last.total,
+        low: last.total,
+        high: last.total,
+    }];
+
+    let mut total = start;
+    let mut after = Duration::ZERO;
+    while after < assumptions.horizon {
+        let step = assumptions.step.min(assumptions.horizon - after);
+        let now = last.at + after;
+
+        let factor: f64 = assumptions
+            .windows
+            .iter()
+            .filter(|w| w.start <= now && now < w.end)
+            .map(|w| w.factor)
+            .product();
+        total += rate * factor * step.as_secs_f64() / HOUR;
+
+        let next = now + step;
+        total += assumptions
+            .injections
+            .iter()
+            .filter(|i| now < i.at && i.at <= next)
+            .map(|i| i.amount.usd())
+            .sum::<f64>();
+
+        after += step;
+        points.push(ProjectedPoint {
+            after,
+            total: Usd::new(total),
+            low: band(total, -1.),
+            high: band(total, 1.),
+        });
+    }
+
+    let mut targets: Vec<(String, Usd)> = campaign
+        .milestones
+        .iter()
+        .filter_map(|m| Some((m.description.clone(), m.amount?)))
+        .chain(std::iter::once(("Goal".to_string(), campaign.goal)))
+        .filter(|(_, amount)| *amount > last.total)
+        .collect();
+    targets.sort_by_key(|(_, amount)| *amount);
+
+    let crossings = targets
+        .into_iter()
+        .map(|(description, amount)| Crossing {
+            after: crossing(&points, amount, |p| p.total),
+            earliest: crossing(&points, amount, |p| p.high),
+            latest: crossing(&points, amount, |p| p.low),
+            description,
+            amount,
+        })
+        .collect();
+
+    Ok(Projection {
+        rate_per_hour: Usd::new(rate),
+        points,
+        crossings,
+    })
+}
+
+/// Dollars per hour between the first and last samples
+fn historical_rate(history: &[Sample]) -> Result<f64, Report> {
+    let (first, last) = (history[0], history[history.len() - 1]);
+    let hours = last
+        .at
+        .duration_since(first.at)
+        .unwrap_or_default()
+        .as_secs_f64()
+        / HOUR;
+
+    if hours == 0. {
+        return Err(Report::msg(
+            "Can't work out a rate from samples at a single time; set rate_per_hour instead",
+        ));
+    }
+
+    Ok((last.total.usd() - first.total.usd()) / hours)
+}
+
+/// When `series` first reaches `amount`, to the second, interpolating within a step
+fn crossing(
+    points: &[ProjectedPoint],
+    amount: Usd,
+    series: impl Fn(&ProjectedPoint) -> Usd,
+) -> Option<Duration> {
+    points.windows(2).find_map(|pair| {
+        let (a, b) = (series(&pair[0]).usd(), series(&pair[1]).usd());
+        if b < amount.usd() {
+            return None;
+        }
+
+        let span = (pair[1].after - pair[0].after).as_secs_f64();
+        let fraction = if b > a {
+            ((amount.usd() - a) / (b - a)).clamp(0., 1.)
+        } else {
+            1.
+        };
+        // Nobody needs a projection to the nanosecond
+        let secs = pair[0].after.as_secs_f64() + span * fraction;
+        Some(Duration::from_secs(secs.round() as u64))
+    })
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    use std::time::UNIX_EPOCH;
+
+    fn hours(h: f64) -> Duration {
+        Duration::from_secs_f64(h * HOUR)
+    }
+
+    fn at(h: f64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_631_851_200) + hours(h)
+    }
+
+    /// $2,000 raised, goal $10,000, with milestones at $5,000 and $8,000
+    fn campaign() -> Campaign {
+        Campaign::builder()
+            .name("Test")
+            .raised(2_000.)
+            .goal(10_000.)
+            .milestone("Eight", 8_000.)
+            .milestone("Already done", 1_000.)
+            .milestone("Five", 5_000.)
+            .build()
+    }
+
+    /// $1,000 an hour for the last two hours
+    fn history() -> Vec<Sample> {
+        vec![
+            Sample {
+                at: at(2.),
+                total: Usd::new(2_000.),
+            },
+            Sample {
+                at: at(0.),
+                total: Usd::ZERO,
+            },
+        ]
+    }
+
+    fn after(projection: &Projection, description: &str) -> Option<Duration> {
+        projection
+            .crossings
+            .iter()
+            .find(|c| c.description == description)
+            .unwrap()
+            .after
+    }
+
+    #[test]
+    fn steady_pace() {
+        let projection = project(&campaign(), &history(), &Assumptions::new(hours(12.))).unwrap();
+
+        assert_eq!(projection.rate_per_hour, Usd::new(1_000.));
+        assert_eq!(projection.points.len(), 12 * 12 + 1);
+
+        let names: Vec<_> = projection
+            .crossings
+            .iter()
+            .map(|c| c.description.as_str())
+            .collect();
+        assert_eq!(names, ["Five", "Eight", "Goal"]);
+
+        assert_eq!(after(&projection, "Five"), Some(hours(3.)));
+        assert_eq!(after(&projection, "Eight"), Some(hours(6.)));
+        assert_eq!(after(&projection, "Goal"), Some(hours(8.)));
+    }
+
+    #[test]
+    fn beyond_the_horizon() {
+        let projection = project(&campaign(), &history(), &Assumptions::new(hours(4.))).unwrap();
+
+        assert_eq!(after(&projection, "Five"), Some(hours(3.)));
+        assert_eq!(after(&projection, "Eight"), None);
+    }
+
+    #[test]
+    fn matching_hour() {
+        // Doubled for the first hour: $2,000 + $2,000 + $1,000 reaches $5,000 after two hours
+        let assumptions = Assumptions {
+            windows: vec![RateWindow {
+                start: at(2.),
+                end: at(3.),
+                factor: 2.,
+            }],
+            ..Assumptions::new(hours(12.))
+        };
+        let projection = project(&campaign(), &history(), &assumptions).unwrap();
+
+        assert_eq!(after(&projection, "Five"), Some(hours(2.)));
+    }
+
+    #[test]
+    fn injection() {
+        // $1,000 arrives half an hour in, leaving $2,000 to go at $1,000 an hour
+        let assumptions = Assumptions {
+            injections: vec![Injection {
+                at: at(2.5),
+                amount: Usd::new(1_000.),
+            }],
+            ..Assumptions::new(hours(12.))
+        };
+        let projection = project(&campaign(), &history(), &assumptions).unwrap();
+
+        assert_eq!(after(&projection, "Five"), Some(hours(2.)));
+    }
+
+    #[test]
+    fn uncertainty_band() {
+        let assumptions = Assumptions {
+            uncertainty: 0.25,
+            ..Assumptions::new(hours(12.))
+        };
+        let projection = project(&campaign(), &history(), &assumptions).unwrap();
+        let five = &projection.crossings[0];
+
+        // $3,000 to go at $1,250 and $750 an hour
+        assert_eq!(five.earliest, Some(hours(2.4)));
+        assert_eq!(five.latest, Some(hours(4.)));
+
+        let last = projection.points.last().unwrap();
+        assert_eq!(last.total, Usd::new(14_000.));
+        assert_eq!(last.low, Usd::new(11_000.));
+        assert_eq!(last.high, Usd::new(17_000.));
+    }
+
+    #[test]
+    fn rate_override() {
+        let assumptions = Assumptions {
+            rate_per_hour: Some(Usd::new(500.)),
+            ..Assumptions::new(hours(12.))
+        };
+        let one_sample = [history()[0]];
+        let projection = project(&campaign(), &one_sample, &assumptions).unwrap();
+
+        assert_eq!(after(&projection, "Five"), Some(hours(6.)));
+    }
+
+    #[test]
+    fn not_enough_history() {
+        let assumptions = Assumptions::new(hours(12.));
+
+        assert!(project(&campaign(), &[], &assumptions).is_err());
+        assert!(project(&campaign(), &history()[..1], &assumptions).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Strict mirrors of the response types, for catching schema drift
+//!
+//! The real model is deliberately lenient: it ignores anything it doesn't know about, so that
+//! Tiltify adding a field doesn't break anyone. These types are the opposite. They accept exactly
+//! the fields our queries select and reject everything else, with the unknown field's name in the
+//! error. They don't hold any data; a response that passes is then parsed into the real model.
+//!
+//! Keep these in sync with the selection in `ops.rs`.
+
+// The fields only exist to be checked, never read
+#![allow(dead_code)]
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ApiResponse {
+    data: Option<ApiData>,
+    errors: Option<Vec<ApiError>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ApiData {
+    campaign: Campaign,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ApiError {
+    message: IgnoredAny,
+    locations: Option<Vec<Location>>,
+    // GraphQL allows errors to carry arbitrary extra data here
+    extensions: Option<IgnoredAny>,
+    path: Option<IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Location {
+    line: IgnoredAny,
+    column: IgnoredAny,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct Campaign {
+    id: Option<IgnoredAny>,
+    name: IgnoredAny,
+    slug: Option<IgnoredAny>,
+    description: IgnoredAny,
+    status: Option<IgnoredAny>,
+    avatar: Option<Avatar>,
+    team: Option<Team>,
+    livestream: Option<Livestream>,
+    cause: Option<Cause>,
+    total_amount_raised: Amount,
+    goal: Amount,
+    original_goal: Option<Amount>,
+    published_at: Option<IgnoredAny>,
+    ends_at: Option<IgnoredAny>,
+    milestones: Option<Vec<Milestone>>,
+    rewards: Option<Vec<Reward>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Reward {
+    name: IgnoredAny,
+    description: Option<IgnoredAny>,
+    amount: Amount,
+    quantity: Option<IgnoredAny>,
+    remaining: Option<IgnoredAny>,
+    active: Option<IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Team {
+    name: IgnoredAny,
+    avatar: Option<Avatar>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Cause {
+    name: IgnoredAny,
+    slug: IgnoredAny,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Livestream {
+    r#type: IgnoredAny,
+    channel: IgnoredAny,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Avatar {
+    src: IgnoredAny,
+    alt: Option<IgnoredAny>,
+    width: Option<IgnoredAny>,
+    height: Option<IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Milestone {
+    id: Option<IgnoredAny>,
+    name: IgnoredAny,
+    amount: Amount,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Amount {
+    currency: Option<IgnoredAny>,
+    value: IgnoredAny,
+}
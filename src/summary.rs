@@ -0,0 +1,265 @@
+use crate::{text, Campaign, CampaignStatus, ListOptions, Milestone, Usd};
+
+use std::fmt;
+
+/// A readable, multi-line summary of a [`Campaign`], from [`Campaign::summary`]
+///
+/// This is what the command line's status listing shows:
+///
+/// ```text
+/// Relay FM for St. Jude 2021!
+/// $22,663.40 of $333,333.33 (6.8%)
+///     ✅            $20,000.00 - Myke and Stephen attempt Flight Simulator again
+///     🤞 41.2%      $55,000.00 - Stephen dissembles his NeXTCube on stream
+/// ```
+///
+/// The alternate form, `{:#}`, adds the campaign's description under its name. There's no
+/// trailing newline, and milestones left off by [`ListOptions::limit`] aren't mentioned.
+#[derive(Clone, Debug)]
+pub struct CampaignSummary<'a> {
+    campaign: &'a Campaign,
+    listing: ListOptions,
+}
+
+impl Campaign {
+    /// Summarize the campaign, listing milestones as `listing` says
+    ///
+    /// `Display` for a `Campaign` is the same thing with the default options: every milestone,
+    /// cheapest first.
+    pub fn summary(&self, listing: &ListOptions) -> CampaignSummary<'_> {
+        CampaignSummary {
+            campaign: self,
+            listing: listing.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Campaign {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.summary(&ListOptions::default()).fmt(f)
+    }
+}
+
+impl fmt::Display for CampaignSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let campaign = self.campaign;
+        let raised = campaign.total_amount_raised;
+
+        write!(f, "{}!", campaign.name)?;
+        if f.alternate() && !campaign.description.is_empty() {
+            write!(f, "\n{}", campaign.description)?;
+        }
+
+        // Before launch the total is always $0, so don't bother showing it
+        if campaign.status == Some(CampaignStatus::Unpublished) {
+            return write!(
+                f,
+                "\nCampaign not yet live — goal {}, {} milestones announced",
+                campaign.goal,
+                campaign.milestones.len()
+            );
+        }
+
+        write!(
+            f,
+            "\n{} of {} ({})",
+            raised,
+            campaign.goal,
+            campaign.percent_raised()
+        )?;
+
+        for milestone in campaign.milestone_page(&self.listing).milestones {
+            let amount = match milestone.amount {
+                Some(amount) => amount,
+                None => {
+                    // No amount yet, so no progress to show either
+                    write!(
+                        f,
+                        "\n    {:8} {} - {}",
+                        "",
+                        text::pad("—", 15, text::Align::Right),
+                        milestone.description
+                    )?;
+                    continue;
+                }
+            };
+
+            if milestone.is_completed(raised) {
+                // Don't show the percent once it's passed
+                write!(f, "\n    ✅ {:5}", "")?;
+            } else {
+                write!(f, "\n    🤞 {:5}", milestone.percent_of(raised))?;
+            }
+            write!(f, " {:15} - {}", amount, milestone.description)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Like `$75,000.00 — Stephen & Myke go to space via KSP`
+///
+/// The format spec applies to the amount, so `{:15}` lines amounts up in a column while the
+/// description runs on as long as it is. A milestone without an amount yet shows `—` instead.
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.amount {
+            Some(amount) => fmt::Display::fmt(&amount, f)?,
+            None => text::pad_formatter(f, "—", text::Align::Right)?,
+        }
+        write!(f, " — {}", self.description)
+    }
+}
+
+/// A [`Milestone`] with whether it's been reached, from [`Milestone::display_with`]
+#[derive(Copy, Clone, Debug)]
+pub struct MilestoneDisplay<'a> {
+    milestone: &'a Milestone,
+    raised: Usd,
+}
+
+impl Milestone {
+    /// Display this milestone marked ✅ if `raised` reaches it, or 🤞 if not
+    ///
+    /// Like `✅ $20,000.00 — Myke and Stephen attempt Flight Simulator again`. The format spec
+    /// applies to the amount, the same as for a plain `Milestone`.
+    pub fn display_with(&self, raised: Usd) -> MilestoneDisplay<'_> {
+        MilestoneDisplay {
+            milestone: self,
+            raised,
+        }
+    }
+}
+
+impl fmt::Display for MilestoneDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.milestone.is_completed(self.raised) {
+            f.write_str("✅ ")?;
+        } else {
+            f.write_str("🤞 ")?;
+        }
+        fmt::Display::fmt(self.milestone, f)
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+    use crate::CompletedPlacement;
+
+    fn example() -> Campaign {
+        Campaign::from_json(include_str!("example-response.json")).unwrap()
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            example().to_string(),
+            indoc::indoc!(
+                "
+                Relay FM for St. Jude 2021!
+                $22,663.40 of $333,333.33 (6.8%)
+                    ✅            $20,000.00 - Myke and Stephen attempt Flight Simulator again
+                    🤞 41.2%      $55,000.00 - Stephen dissembles his NeXTCube on stream
+                    🤞 30.2%      $75,000.00 - Stephen & Myke go to space via KSP
+                    🤞 11.6%     $196,060.44 - $1 million raised in 3 years!"
+            )
+        );
+    }
+
+    #[test]
+    fn alternate_has_the_description() {
+        let mut campaign = example();
+        campaign.description = "For the kids".to_string();
+        campaign.total_amount_raised = Usd::new(55_000.);
+        let listing = ListOptions {
+            completed: Some(CompletedPlacement::Last),
+            limit: Some(2),
+            ..ListOptions::default()
+        };
+
+        assert_eq!(
+            format!("{:#}", campaign.summary(&listing)),
+            indoc::indoc!(
+                "
+                Relay FM for St. Jude 2021!
+                For the kids
+                $55,000.00 of $333,333.33 (16.5%)
+                    🤞 73.3%      $75,000.00 - Stephen & Myke go to space via KSP
+                    🤞 28.1%     $196,060.44 - $1 million raised in 3 years!"
+            )
+        );
+    }
+
+    #[test]
+    fn milestones() {
+        let campaign = example();
+        let ksp = &campaign.milestones[0];
+
+        assert_eq!(
+            ksp.to_string(),
+            "$75,000.00 — Stephen & Myke go to space via KSP"
+        );
+        assert_eq!(
+            format!("{:15}", ksp),
+            "     $75,000.00 — Stephen & Myke go to space via KSP"
+        );
+        assert_eq!(
+            format!("{:<12}|", campaign.milestones[3]),
+            "$196,060.44  — $1 million raised in 3 years!|"
+        );
+
+        // The width is only for the amount, long descriptions aren't cut or padded
+        let long = Milestone {
+            id: None,
+            description: "Myke reads the entire terms of service for every app on his phone"
+                .repeat(3),
+            amount: None,
+        };
+        assert_eq!(
+            format!("{:5}", long),
+            format!("    — — {}", long.description)
+        );
+
+        let raised = campaign.total_amount_raised;
+        let lines: Vec<_> = campaign
+            .sorted_milestones()
+            .iter()
+            .map(|m| format!("{:12}", m.display_with(raised)))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "✅   $20,000.00 — Myke and Stephen attempt Flight Simulator again",
+                "🤞   $55,000.00 — Stephen dissembles his NeXTCube on stream",
+                "🤞   $75,000.00 — Stephen & Myke go to space via KSP",
+                "🤞  $196,060.44 — $1 million raised in 3 years!",
+            ]
+        );
+    }
+
+    #[test]
+    fn unpriced_and_unpublished() {
+        let campaign =
+            Campaign::from_json(include_str!("example-response-null-amount.json")).unwrap();
+        assert_eq!(
+            campaign.to_string(),
+            indoc::indoc!(
+                "
+                Relay FM for St. Jude 2021!
+                $22,663.40 of $333,333.33 (6.8%)
+                    ✅            $20,000.00 - Myke and Stephen attempt Flight Simulator again
+                    🤞 41.2%      $55,000.00 - Stephen dissembles his NeXTCube on stream
+                                           — - Something secret"
+            )
+        );
+
+        let campaign =
+            Campaign::from_json(include_str!("example-response-unpublished.json")).unwrap();
+        assert_eq!(
+            campaign.to_string(),
+            "Relay FM for St. Jude 2022!\n\
+             Campaign not yet live — goal $333,333.33, 2 milestones announced"
+        );
+    }
+}
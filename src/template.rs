@@ -0,0 +1,243 @@
+//! Tiny text templates for milestone and progress announcements
+//!
+//! Bots that post "we did it!" messages want their own wording. A [`Template`] is plain text
+//! with `{variable}` placeholders, checked when it's parsed so that a typo fails at startup
+//! rather than mid-stream:
+//!
+//! ```rust
+//! use relay_st_jude::template::Template;
+//!
+//! let template = Template::parse(
+//!     "🎉 {milestone.description} unlocked at {milestone.amount}! Total: {campaign.raised}",
+//! )
+//! .unwrap();
+//! ```
+//!
+//! Write `{{` and `}}` for literal braces.
+//!
+//! # Variables
+//! | Variable                | Example                      |
+//! |-------------------------|------------------------------|
+//! | `campaign.name`         | Relay FM for St. Jude 2021   |
+//! | `campaign.raised`       | $22,663.40                   |
+//! | `campaign.goal`         | $333,333.33                  |
+//! | `campaign.percent`      | 6.8%                         |
+//! | `milestone.description` | Myke and Stephen attempt ... |
+//! | `milestone.amount`      | $20,000.00                   |
+//!
+//! The `milestone.*` variables are empty when rendering without a milestone, and
+//! `milestone.amount` is `—` for a milestone that doesn't have an amount yet.
+
+use crate::{Campaign, Milestone, Report};
+
+use std::fmt::Write;
+
+/// Every variable a template may use, in the order they're documented
+pub const VARIABLES: &[&str] = &[
+    "campaign.name",
+    "campaign.raised",
+    "campaign.goal",
+    "campaign.percent",
+    "milestone.description",
+    "milestone.amount",
+];
+
+/// A parsed template, ready to [`render`](Template::render)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    Text(String),
+
+    /// An index into [`VARIABLES`]
+    Var(usize),
+}
+
+impl Template {
+    /// Parse `source`, rejecting unknown variables and unbalanced braces
+    pub fn parse(source: &str) -> Result<Self, Report> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|&(_, c)| c) == Some('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek().map(|&(_, c)| c) == Some('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let rest = &source[i + 1..];
+                    let len = rest.find('}').ok_or_else(|| {
+                        Report::msg(format!(
+                            "Unclosed '{{' at byte {} of template; write '{{{{' for a literal brace",
+                            i
+                        ))
+                    })?;
+                    let name = rest[..len].trim();
+                    let var = VARIABLES.iter().position(|&v| v == name).ok_or_else(|| {
+                        Report::msg(format!(
+                            "Unknown template variable {{{}}} at byte {}; expected one of: {}",
+                            name,
+                            i,
+                            VARIABLES.join(", ")
+                        ))
+                    })?;
+
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Var(var));
+
+                    // Skip the name and the closing brace
+                    for _ in rest[..=len].chars() {
+                        chars.next();
+                    }
+                }
+                '}' => {
+                    return Err(Report::msg(format!(
+                        "Unmatched '}}' at byte {} of template; write '}}}}' for a literal brace",
+                        i
+                    )))
+                }
+                _ => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Fill in the template for `campaign`, and the `milestone` being announced, if any
+    pub fn render(&self, campaign: &Campaign, milestone: Option<&Milestone>) -> String {
+        let mut out = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Var(var) => {
+                    // Writing to a String can't fail
+                    let _ = match VARIABLES[*var] {
+                        "campaign.name" => write!(out, "{}", campaign.name),
+                        "campaign.raised" => write!(out, "{}", campaign.total_amount_raised),
+                        "campaign.goal" => write!(out, "{}", campaign.goal),
+                        "campaign.percent" => write!(out, "{}", campaign.percent_raised()),
+                        "milestone.description" => match milestone {
+                            Some(m) => write!(out, "{}", m.description),
+                            None => Ok(()),
+                        },
+                        "milestone.amount" => match milestone.map(|m| m.amount) {
+                            Some(Some(amount)) => write!(out, "{}", amount),
+                            Some(None) => write!(out, "—"),
+                            None => Ok(()),
+                        },
+                        _ => unreachable!("every variable is handled"),
+                    };
+                }
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn example() -> Campaign {
+        Campaign::from_json(include_str!("example-response.json")).unwrap()
+    }
+
+    fn parse_error(source: &str) -> String {
+        Template::parse(source).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn milestone_announcement() {
+        let campaign = example();
+        let milestone = campaign
+            .milestones
+            .iter()
+            .find(|m| m.description.contains("Flight Simulator"))
+            .unwrap();
+
+        let template = Template::parse(
+            "🎉 {milestone.description} unlocked at {milestone.amount}! \
+             Total: {campaign.raised} ({campaign.percent})",
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.render(&campaign, Some(milestone)),
+            "🎉 Myke and Stephen attempt Flight Simulator again unlocked at $20,000.00! \
+             Total: $22,663.40 (6.8%)"
+        );
+    }
+
+    #[test]
+    fn progress_without_milestone() {
+        let template = Template::parse(
+            "{campaign.name}: {campaign.raised} of {campaign.goal}{milestone.amount}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.render(&example(), None),
+            "Relay FM for St. Jude 2021: $22,663.40 of $333,333.33"
+        );
+    }
+
+    #[test]
+    fn unpriced_milestone() {
+        let milestone = Milestone {
+            id: None,
+            description: "Something secret".to_string(),
+            amount: None,
+        };
+        let template = Template::parse("{milestone.description}: {milestone.amount}").unwrap();
+
+        assert_eq!(
+            template.render(&example(), Some(&milestone)),
+            "Something secret: —"
+        );
+    }
+
+    #[test]
+    fn escaped_braces() {
+        let template = Template::parse("{{literal}} { campaign.name } }}").unwrap();
+
+        assert_eq!(
+            template.render(&example(), None),
+            "{literal} Relay FM for St. Jude 2021 }"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_error("Total: {campaign.total}"),
+            "Unknown template variable {campaign.total} at byte 7; expected one of: \
+             campaign.name, campaign.raised, campaign.goal, campaign.percent, \
+             milestone.description, milestone.amount"
+        );
+        assert_eq!(
+            parse_error("Total: {campaign.raised"),
+            "Unclosed '{' at byte 7 of template; write '{{' for a literal brace"
+        );
+        assert_eq!(
+            parse_error("100% }"),
+            "Unmatched '}' at byte 5 of template; write '}}' for a literal brace"
+        );
+    }
+}
@@ -0,0 +1,492 @@
+//! Terminal display width math
+//!
+//! Padding with `{:15}` counts `char`s, which goes wrong as soon as a milestone description has
+//! CJK text (two columns per character), emoji (two columns, sometimes built out of several
+//! characters), or combining accents (zero columns). Everything in this crate that lines text up
+//! in columns goes through [`display_width`] instead.
+//!
+//! This is a small approximation of [UAX #11](https://www.unicode.org/reports/tr11/) and the
+//! emoji rules terminals actually use, not a full implementation. Ambiguous-width characters are
+//! treated as narrow.
+
+use crate::Percent;
+
+use std::fmt;
+
+/// Zero width joiner, used to glue emoji together like 👩‍🚀
+const ZWJ: char = '\u{200D}';
+
+/// Variation selector 16: "show the previous character as an emoji"
+const VS16: char = '\u{FE0F}';
+
+/// The number of terminal columns `s` takes up
+///
+/// ```rust
+/// use relay_st_jude::text::display_width;
+///
+/// assert_eq!(display_width("KSP"), 3);
+/// assert_eq!(display_width("寄付"), 4);
+/// assert_eq!(display_width("🧑‍🚀"), 2);
+/// assert_eq!(display_width("e\u{301}"), 1);
+/// ```
+pub fn display_width(s: &str) -> usize {
+    let mut width = 0;
+
+    // Width of the last thing we counted, if it was an emoji that a ZWJ or modifier can extend
+    let mut prev_emoji: Option<usize> = None;
+    let mut after_zwj = false;
+    // Regional indicators pair up into flags, so only count every other one
+    let mut pending_flag = false;
+
+    for c in s.chars() {
+        if c == ZWJ {
+            after_zwj = prev_emoji.is_some();
+            continue;
+        }
+
+        if after_zwj && is_emoji(c) {
+            // Part of a ZWJ sequence, like 👩‍🚀. The whole thing is one emoji two columns wide.
+            after_zwj = false;
+            continue;
+        }
+        after_zwj = false;
+
+        if c == VS16 {
+            // Text-style symbols like ❤ become two columns wide when asked to be emoji
+            if prev_emoji == Some(1) {
+                width += 1;
+                prev_emoji = Some(2);
+            }
+            continue;
+        }
+
+        if is_emoji_modifier(c) && prev_emoji.is_some() {
+            // Skin tones don't take up space of their own
+            continue;
+        }
+
+        if is_regional_indicator(c) {
+            if !pending_flag {
+                width += 2;
+            }
+            pending_flag = !pending_flag;
+            prev_emoji = Some(2);
+            continue;
+        }
+        pending_flag = false;
+
+        let w = char_width(c);
+        width += w;
+        prev_emoji = if is_emoji(c) { Some(w) } else { None };
+    }
+
+    width
+}
+
+/// How to line up text padded by [`pad`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Align {
+    /// Padding goes on the right
+    Left,
+    /// Padding goes on the left
+    Right,
+    /// Padding is split, with any odd column on the right
+    Center,
+}
+
+/// Pad `s` with spaces to be at least `width` columns wide
+///
+/// Text that's already wider than `width` is returned as-is, the same as `format!` does.
+pub fn pad(s: &str, width: usize, align: Align) -> String {
+    pad_with(s, width, align, ' ')
+}
+
+/// Like [`pad`], but padding with `fill` instead of spaces
+pub fn pad_with(s: &str, width: usize, align: Align, fill: char) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    let (left, right) = match align {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2),
+    };
+
+    let fill = |n: usize| fill.to_string().repeat(n);
+    format!("{}{}{}", fill(left), s, fill(right))
+}
+
+/// Write `s` honoring `f`'s width, fill, and alignment, lining up to `default` if it has none
+///
+/// This is [`Formatter::pad`](std::fmt::Formatter::pad), but counting columns with
+/// [`display_width`], and leaving the precision for the caller to use.
+pub(crate) fn pad_formatter(f: &mut fmt::Formatter, s: &str, default: Align) -> fmt::Result {
+    let width = match f.width() {
+        Some(width) => width,
+        // No width requested? Write it direct
+        None => return f.write_str(s),
+    };
+    let align = match f.align() {
+        Some(fmt::Alignment::Left) => Align::Left,
+        Some(fmt::Alignment::Right) => Align::Right,
+        Some(fmt::Alignment::Center) => Align::Center,
+        None => default,
+    };
+
+    f.write_str(&pad_with(s, width, align, f.fill()))
+}
+
+/// How to draw a [`progress_bar`]
+///
+/// ```rust
+/// use relay_st_jude::text::ProgressBar;
+///
+/// assert_eq!(ProgressBar::default().render(0.375, 10), "███░░░░░░░ 38%");
+/// assert_eq!(ProgressBar::ASCII.render(0.375, 10), "###------- 38%");
+///
+/// let bare = ProgressBar {
+///     percent: false,
+///     ..ProgressBar::ASCII
+/// };
+/// assert_eq!(bare.render(1.5, 4), "####");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ProgressBar {
+    /// The character for the done part of the bar
+    pub filled: char,
+
+    /// The character for the rest of it
+    pub empty: char,
+
+    /// Whether to follow the bar with the percentage, like ` 38%`
+    ///
+    /// This is the real percentage, so it can go past 100% even though the bar can't.
+    pub percent: bool,
+}
+
+impl ProgressBar {
+    /// Unicode blocks, `███░░░░░░░`, with the percentage. This is the default.
+    pub const UNICODE: Self = Self {
+        filled: '█',
+        empty: '░',
+        percent: true,
+    };
+
+    /// Plain ASCII, `###-------`, with the percentage, for places without good fonts
+    pub const ASCII: Self = Self {
+        filled: '#',
+        empty: '-',
+        percent: true,
+    };
+
+    /// A bar exactly `width` cells long, filled `fraction` of the way
+    ///
+    /// `fraction` is clamped between 0.0 and 1.0, and NaN counts as 0.0. Partial cells round
+    /// down, so the bar is only completely full once `fraction` reaches 1.0.
+    pub fn render(&self, fraction: f64, width: usize) -> String {
+        // A hair of slack so that 0.57 of 100 cells is 57, not 56.99999999999999
+        let cells = width as f64 * fraction.clamp(0., 1.) + 1e-9;
+        let filled = if cells.is_nan() {
+            0
+        } else {
+            (cells.floor() as usize).min(width)
+        };
+
+        let mut bar = self.filled.to_string().repeat(filled);
+        bar.push_str(&self.empty.to_string().repeat(width - filled));
+        if self.percent {
+            bar.push_str(&format!(" {:.0}", Percent(100. * fraction)));
+        }
+        bar
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::UNICODE
+    }
+}
+
+/// A bar exactly `width` cells long, filled `fraction` of the way, then the percentage
+///
+/// This is [`ProgressBar::default`]. See [`ProgressBar::render`] for the details.
+pub fn progress_bar(fraction: f64, width: usize) -> String {
+    ProgressBar::default().render(fraction, width)
+}
+
+fn char_width(c: char) -> usize {
+    if c.is_control() || is_zero_width(c) {
+        0
+    } else if is_wide(c) {
+        2
+    } else {
+        1
+    }
+}
+
+fn in_ranges(c: char, ranges: &[(u32, u32)]) -> bool {
+    let c = c as u32;
+    ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi)
+}
+
+fn is_zero_width(c: char) -> bool {
+    in_ranges(
+        c,
+        &[
+            (0x0300, 0x036F), // Combining Diacritical Marks
+            (0x0483, 0x0489), // Cyrillic combining marks
+            (0x0591, 0x05BD), // Hebrew points
+            (0x0610, 0x061A), // Arabic marks
+            (0x064B, 0x065F),
+            (0x0E31, 0x0E31), // Thai
+            (0x0E34, 0x0E3A),
+            (0x0E47, 0x0E4E),
+            (0x1AB0, 0x1AFF),   // Combining Diacritical Marks Extended
+            (0x1DC0, 0x1DFF),   // Combining Diacritical Marks Supplement
+            (0x200B, 0x200F),   // Zero width space, joiners, direction marks
+            (0x20D0, 0x20FF),   // Combining marks for symbols
+            (0x3099, 0x309A),   // Combining kana voicing marks
+            (0xFE00, 0xFE0F),   // Variation selectors
+            (0xFE20, 0xFE2F),   // Combining half marks
+            (0xFEFF, 0xFEFF),   // Byte order mark
+            (0xE0000, 0xE0FFF), // Tags and variation selectors supplement
+        ],
+    )
+}
+
+fn is_wide(c: char) -> bool {
+    in_ranges(
+        c,
+        &[
+            (0x1100, 0x115F), // Hangul Jamo
+            (0x231A, 0x231B), // ⌚⌛
+            (0x23E9, 0x23EC),
+            (0x23F0, 0x23F0),
+            (0x23F3, 0x23F3),
+            (0x25FD, 0x25FE),
+            (0x2614, 0x2615),
+            (0x2648, 0x2653),
+            (0x267F, 0x267F),
+            (0x2693, 0x2693),
+            (0x26A1, 0x26A1),
+            (0x26AA, 0x26AB),
+            (0x26BD, 0x26BE),
+            (0x26C4, 0x26C5),
+            (0x26CE, 0x26CE),
+            (0x26D4, 0x26D4),
+            (0x26EA, 0x26EA),
+            (0x26F2, 0x26F3),
+            (0x26F5, 0x26F5),
+            (0x26FA, 0x26FA),
+            (0x26FD, 0x26FD),
+            (0x2705, 0x2705), // ✅
+            (0x270A, 0x270B),
+            (0x2728, 0x2728),
+            (0x274C, 0x274C),
+            (0x274E, 0x274E),
+            (0x2753, 0x2755),
+            (0x2757, 0x2757),
+            (0x2795, 0x2797),
+            (0x27B0, 0x27B0),
+            (0x27BF, 0x27BF),
+            (0x2B1B, 0x2B1C),
+            (0x2B50, 0x2B50),
+            (0x2B55, 0x2B55),
+            (0x2E80, 0x303E), // CJK radicals, symbols and punctuation
+            (0x3041, 0x33FF), // Kana, CJK compatibility
+            (0x3400, 0x4DBF), // CJK Extension A
+            (0x4E00, 0x9FFF), // CJK Unified Ideographs
+            (0xA000, 0xA4CF), // Yi
+            (0xA960, 0xA97F), // Hangul Jamo Extended-A
+            (0xAC00, 0xD7A3), // Hangul Syllables
+            (0xF900, 0xFAFF), // CJK Compatibility Ideographs
+            (0xFE10, 0xFE19), // Vertical forms
+            (0xFE30, 0xFE6F), // CJK compatibility forms, small forms
+            (0xFF00, 0xFF60), // Fullwidth forms
+            (0xFFE0, 0xFFE6),
+            (0x16FE0, 0x16FE4),
+            (0x17000, 0x18AFF), // Tangut
+            (0x1B000, 0x1B16F), // Kana supplement
+            (0x1F004, 0x1F004),
+            (0x1F0CF, 0x1F0CF),
+            (0x1F18E, 0x1F18E),
+            (0x1F191, 0x1F19A),
+            (0x1F200, 0x1F251),
+            (0x1F260, 0x1F265),
+            (0x1F300, 0x1F320), // Misc symbols and pictographs
+            (0x1F32D, 0x1F335),
+            (0x1F337, 0x1F37C),
+            (0x1F37E, 0x1F393),
+            (0x1F3A0, 0x1F3CA),
+            (0x1F3CF, 0x1F3D3),
+            (0x1F3E0, 0x1F3F0),
+            (0x1F3F4, 0x1F3F4),
+            (0x1F3F8, 0x1F43E),
+            (0x1F440, 0x1F440),
+            (0x1F442, 0x1F4FC),
+            (0x1F4FF, 0x1F53D),
+            (0x1F54B, 0x1F54E),
+            (0x1F550, 0x1F567),
+            (0x1F57A, 0x1F57A),
+            (0x1F595, 0x1F596),
+            (0x1F5A4, 0x1F5A4),
+            (0x1F5FB, 0x1F64F), // ... and emoticons
+            (0x1F680, 0x1F6C5), // Transport and map symbols
+            (0x1F6CC, 0x1F6CC),
+            (0x1F6D0, 0x1F6D2),
+            (0x1F6D5, 0x1F6D7),
+            (0x1F6DC, 0x1F6DF),
+            (0x1F6EB, 0x1F6EC),
+            (0x1F6F4, 0x1F6FC),
+            (0x1F7E0, 0x1F7EB),
+            (0x1F7F0, 0x1F7F0),
+            (0x1F90C, 0x1F93A), // Supplemental symbols and pictographs
+            (0x1F93C, 0x1F945),
+            (0x1F947, 0x1F9FF),
+            (0x1FA70, 0x1FAFF), // Symbols and pictographs extended-A
+            (0x20000, 0x2FFFD), // CJK Extensions B and beyond
+            (0x30000, 0x3FFFD),
+        ],
+    )
+}
+
+/// Anything that can take part in an emoji sequence
+///
+/// This includes text-style symbols like ❤ and ☕, which VS16 can promote to wide emoji.
+fn is_emoji(c: char) -> bool {
+    in_ranges(
+        c,
+        &[
+            (0x00A9, 0x00A9), // ©
+            (0x00AE, 0x00AE), // ®
+            (0x2190, 0x21FF), // Arrows
+            (0x2300, 0x23FF), // Misc technical
+            (0x2600, 0x27BF), // Misc symbols and dingbats
+            (0x2B00, 0x2BFF), // Misc symbols and arrows
+            (0x1F000, 0x1FAFF),
+        ],
+    )
+}
+
+fn is_emoji_modifier(c: char) -> bool {
+    in_ranges(c, &[(0x1F3FB, 0x1F3FF)])
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    in_ranges(c, &[(0x1F1E6, 0x1F1FF)])
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    #[test]
+    fn widths() {
+        #[rustfmt::skip]
+        let cases: &[(&str, usize)] = &[
+            ("", 0),
+            ("Stephen & Myke go to space via KSP", 34),
+            // CJK is two columns per character
+            ("ステファンとマイク", 18),
+            ("募金Goal", 8),
+            ("한국어", 6),
+            ("ｆｕｌｌ", 8),
+            // Emoji, including the ones the CLI prints
+            ("✅", 2),
+            ("🤞", 2),
+            ("🎇🇺🇸🦅🎆", 8),
+            // ZWJ sequences are one emoji
+            ("👩‍🚀", 2),
+            ("👨‍👩‍👧‍👦", 2),
+            ("🏳️‍🌈", 2),
+            // Skin tones and VS16
+            ("👍🏽", 2),
+            ("❤️", 2),
+            ("❤", 1),
+            // Combining characters take no space
+            ("e\u{301}", 1),
+            ("Pok\u{e9}mon", 7),
+            ("Poke\u{301}mon", 7),
+            ("a\u{20DD}", 1),
+            // Controls don't either
+            ("tab\t", 3),
+        ];
+
+        for &(s, expected) in cases {
+            assert_eq!(display_width(s), expected, "{:?}", s);
+        }
+    }
+
+    /// Line up a little table of descriptions, like the CLI does, and make sure every row ends
+    /// in the same column
+    #[test]
+    fn alignment() {
+        let descriptions = [
+            "Myke and Stephen attempt Flight Simulator again",
+            "マイクとステファンがもう一度フライトシミュレーターに挑戦",
+            "🚀 Stephen & Myke go to space",
+            "👩‍🚀 Astronaut 👨‍👩‍👧‍👦",
+            "Cafe\u{301} stream 🇺🇸",
+        ];
+
+        for width in [10, 40, 60] {
+            for align in [Align::Left, Align::Right, Align::Center] {
+                for d in &descriptions {
+                    let padded = pad(d, width, align);
+                    assert_eq!(
+                        display_width(&padded),
+                        display_width(d).max(width),
+                        "{:?} at {} {:?}",
+                        d,
+                        width,
+                        align
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn padding_snapshots() {
+        assert_eq!(pad("寄付", 6, Align::Left), "寄付  ");
+        assert_eq!(pad("寄付", 6, Align::Right), "  寄付");
+        assert_eq!(pad("寄付", 7, Align::Center), " 寄付  ");
+        assert_eq!(pad("👩‍🚀", 4, Align::Right), "  👩‍🚀");
+        assert_eq!(pad("e\u{301}", 3, Align::Left), "e\u{301}  ");
+        assert_eq!(pad("too wide", 3, Align::Right), "too wide");
+        assert_eq!(pad_with("寄付", 7, Align::Center, '*'), "*寄付**");
+    }
+
+    /// Every bar is exactly as wide as asked, with partial cells rounding down
+    #[test]
+    fn progress_bars() {
+        let bare = ProgressBar {
+            percent: false,
+            ..ProgressBar::ASCII
+        };
+        let filled = |fraction: f64, width: usize| {
+            let bar = bare.render(fraction, width);
+            assert_eq!(bar.chars().count(), width, "{:?}", bar);
+            bar.matches('#').count()
+        };
+
+        assert_eq!(filled(0., 10), 0);
+        assert_eq!(filled(0.099, 10), 0);
+        assert_eq!(filled(0.1, 10), 1);
+        assert_eq!(filled(0.7, 10), 7);
+        assert_eq!(filled(0.57, 100), 57);
+        assert_eq!(filled(0.999, 10), 9);
+        assert_eq!(filled(1., 10), 10);
+        assert_eq!(filled(1. / 3., 3), 1);
+        assert_eq!(filled(2. / 3., 3), 2);
+
+        // Out of range
+        assert_eq!(filled(-0.5, 10), 0);
+        assert_eq!(filled(1.5, 10), 10);
+        assert_eq!(filled(f64::NAN, 10), 0);
+        assert_eq!(filled(f64::INFINITY, 10), 10);
+        assert_eq!(filled(0.5, 0), 0);
+
+        assert_eq!(progress_bar(0.068, 10), "░░░░░░░░░░ 7%");
+        assert_eq!(progress_bar(1.123, 5), "█████ 112%");
+        assert_eq!(display_width(&progress_bar(0.5, 8)), 8 + " 50%".len());
+    }
+}
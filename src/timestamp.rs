@@ -0,0 +1,357 @@
+use crate::Campaign;
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serializer};
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+impl Campaign {
+    /// Whether the campaign is over at `now`
+    ///
+    /// Campaigns without an end date never end, and neither do campaigns fetched without
+    /// [`CampaignQuery::Details`](crate::CampaignQuery::Details), which leaves the date out.
+    pub fn has_ended(&self, now: SystemTime) -> bool {
+        self.ends_at.is_some_and(|ends_at| ends_at <= now)
+    }
+
+    /// How long until the campaign ends, counting from `now`
+    ///
+    /// This is zero once the campaign has ended, and `None` if it doesn't have an end date.
+    pub fn time_remaining(&self, now: SystemTime) -> Option<Duration> {
+        let ends_at = self.ends_at?;
+        Some(ends_at.duration_since(now).unwrap_or(Duration::ZERO))
+    }
+}
+
+/// Format `time` as an RFC 3339 UTC timestamp, to the second
+pub(crate) fn rfc3339(time: SystemTime) -> String {
+    // Times before 1970 aren't going to come from a fetch
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+/// Like [`rfc3339`], but keeping milliseconds when there are any
+pub(crate) fn rfc3339_millis(time: SystemTime) -> String {
+    let seconds = rfc3339(time);
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_millis())
+        .unwrap_or(0);
+
+    if millis == 0 {
+        seconds
+    } else {
+        format!("{}.{:03}Z", seconds.trim_end_matches('Z'), millis)
+    }
+}
+
+/// Parse an ISO 8601 timestamp, like `2021-09-17T16:00:00Z` or `2021-09-17T12:00:00.000-04:00`
+///
+/// A `T` or a space can separate the date and time. Fractions of a second are kept to the
+/// nanosecond, and a timestamp without an offset is taken to be UTC.
+pub(crate) fn parse(s: &str) -> Result<SystemTime, String> {
+    let invalid = || format!("Invalid timestamp {:?}, expected ISO 8601", s);
+    let number = |digits: &str| -> Result<i64, String> {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        digits.parse().map_err(|_| invalid())
+    };
+
+    let s = s.trim();
+    let bytes = s.as_bytes();
+    if s.len() < 19 || !s.is_char_boundary(19) || !matches!(bytes[10], b'T' | b't' | b' ') {
+        return Err(invalid());
+    }
+    for (i, separator) in [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')] {
+        if bytes[i] != separator {
+            return Err(invalid());
+        }
+    }
+
+    let (year, month, day) = (number(&s[0..4])?, number(&s[5..7])?, number(&s[8..10])?);
+    let (hour, minute, second) = (
+        number(&s[11..13])?,
+        number(&s[14..16])?,
+        number(&s[17..19])?,
+    );
+    // Allow a leap second, and fold it into the next one
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return Err(invalid());
+    }
+
+    let mut rest = &s[19..];
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.').or_else(|| rest.strip_prefix(',')) {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(invalid());
+        }
+        // Anything past nanoseconds is dropped
+        let kept = &fraction[..digits.min(9)];
+        nanos = number(kept)? * 10_i64.pow(9 - kept.len() as u32);
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(invalid()),
+            };
+            let offset = rest[1..].replace(':', "");
+            if offset.len() != 4 || !offset.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let (hours, minutes) = (number(&offset[..2])?, number(&offset[2..])?);
+            if hours > 23 || minutes > 59 {
+                return Err(invalid());
+            }
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    let secs =
+        days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset;
+    Ok(from_unix(secs, nanos as u32))
+}
+
+/// A time `secs` seconds and `nanos` nanoseconds after 1970, where `secs` may be negative
+fn from_unix(secs: i64, nanos: u32) -> SystemTime {
+    let whole = Duration::from_secs(secs.unsigned_abs());
+    let time = if secs >= 0 {
+        UNIX_EPOCH + whole
+    } else {
+        UNIX_EPOCH - whole
+    };
+    time + Duration::from_nanos(nanos.into())
+}
+
+/// Parse a timestamp that may be `null`, an ISO 8601 string, or milliseconds since 1970
+///
+/// Milliseconds can be a JSON number or a string of digits.
+pub(crate) fn deserialize_optional<'de, D>(deserializer: D) -> Result<Option<SystemTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawTimestamp {
+        Millis(i64),
+        Str(String),
+    }
+
+    let millis = |millis: i64| {
+        let (secs, millis) = (millis.div_euclid(1000), millis.rem_euclid(1000));
+        from_unix(secs, millis as u32 * 1_000_000)
+    };
+
+    Ok(match Option::<RawTimestamp>::deserialize(deserializer)? {
+        None => None,
+        Some(RawTimestamp::Millis(n)) => Some(millis(n)),
+        Some(RawTimestamp::Str(s)) => match s.trim().parse::<i64>() {
+            Ok(n) => Some(millis(n)),
+            Err(_) => Some(parse(&s).map_err(de::Error::custom)?),
+        },
+    })
+}
+
+/// Write a timestamp back out as RFC 3339, or `null`
+pub(crate) fn serialize_optional<S>(
+    time: &Option<SystemTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match time {
+        Some(time) => serializer.serialize_str(&rfc3339_millis(*time)),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) in the proleptic Gregorian calendar
+///
+/// This is Howard Hinnant's `civil_from_days`, from
+/// <https://howardhinnant.github.io/date_algorithms.html>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// The inverse of [`civil_from_days`], Hinnant's `days_from_civil`
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod t {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn timestamps() {
+        let rfc3339 = |secs| rfc3339(at(secs));
+
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_709_251_199), "2024-02-29T23:59:59Z");
+
+        let precise = at(1_631_894_400) + Duration::from_millis(250);
+        assert_eq!(rfc3339_millis(precise), "2021-09-17T16:00:00.250Z");
+        assert_eq!(rfc3339_millis(at(1_631_894_400)), "2021-09-17T16:00:00Z");
+    }
+
+    #[test]
+    fn parses() {
+        let podcastathon = at(1_631_894_400);
+        for s in [
+            "2021-09-17T16:00:00Z",
+            "2021-09-17T16:00:00.000000Z",
+            "2021-09-17t16:00:00z",
+            "2021-09-17 16:00:00",
+            "2021-09-17T12:00:00-04:00",
+            "2021-09-17T21:30:00+0530",
+        ] {
+            assert_eq!(parse(s), Ok(podcastathon), "{}", s);
+        }
+
+        assert_eq!(
+            parse("2021-09-17T16:00:00.123456789123Z"),
+            Ok(podcastathon + Duration::from_nanos(123_456_789))
+        );
+        assert_eq!(
+            parse("1969-12-31T23:59:59Z"),
+            Ok(UNIX_EPOCH - Duration::from_secs(1))
+        );
+        assert_eq!(parse("2000-02-29T00:00:00Z"), Ok(at(951_782_400)));
+
+        for s in [
+            "",
+            "2021-09-17",
+            "2021-09-17T16:00",
+            "2021-13-01T00:00:00Z",
+            "2021-02-29T00:00:00Z",
+            "2021-09-17T24:00:00Z",
+            "2021-09-17T16:00:00.Z",
+            "2021-09-17T16:00:00+4",
+            "2021-09-17T16:00:00 EST",
+            "2021-09-17T16:+0:00Z",
+            "2021-09-17T16:00:00+aé1",
+            "２０２１-09-17T16:00:00Z",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn civil_days_round_trip() {
+        for days in (-800_000..800_000).step_by(997) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month.into(), day.into()), days);
+        }
+    }
+
+    #[test]
+    fn campaign_dates() {
+        let campaign = Campaign::from_json(include_str!("example-response-schedule.json")).unwrap();
+        assert_eq!(campaign.published_at, Some(at(1_630_468_800)));
+        assert_eq!(campaign.ends_at, Some(at(1_633_060_800)));
+
+        let before = at(1_631_894_400);
+        assert!(!campaign.has_ended(before));
+        assert_eq!(
+            campaign.time_remaining(before),
+            Some(Duration::from_secs(1_166_400))
+        );
+
+        let after = at(1_633_060_801);
+        assert!(campaign.has_ended(after));
+        assert!(campaign.has_ended(at(1_633_060_800)));
+        assert_eq!(campaign.time_remaining(after), Some(Duration::ZERO));
+
+        // No end date, no countdown
+        let campaign = Campaign::from_json(include_str!("example-response.json")).unwrap();
+        assert_eq!(campaign.published_at, None);
+        assert!(!campaign.has_ended(after));
+        assert_eq!(campaign.time_remaining(after), None);
+    }
+
+    #[test]
+    fn epoch_millis() {
+        let json = |published: &str, ends: &str| {
+            format!(
+                r#"{{"data": {{"campaign": {{"name": "A", "publishedAt": {}, "endsAt": {},
+                    "totalAmountRaised": {{"value": "1"}}, "goal": {{"value": "2"}}}}}}}}"#,
+                published, ends
+            )
+        };
+
+        let campaign = Campaign::from_json(&json("1630468800000", "\"1633060800250\"")).unwrap();
+        assert_eq!(campaign.published_at, Some(at(1_630_468_800)));
+        assert_eq!(
+            campaign.ends_at,
+            Some(at(1_633_060_800) + Duration::from_millis(250))
+        );
+
+        let campaign = Campaign::from_json(&json("null", "-1500")).unwrap();
+        assert_eq!(campaign.published_at, None);
+        assert_eq!(
+            campaign.ends_at,
+            Some(UNIX_EPOCH - Duration::from_millis(1500))
+        );
+
+        let err = Campaign::from_json(&json("\"next tuesday\"", "null")).unwrap_err();
+        assert!(format!("{:?}", err).contains("next tuesday"), "{:?}", err);
+    }
+}
@@ -0,0 +1,526 @@
+//! Snapshot the crate's public API, so that changes to it are always deliberate
+//!
+//! This builds rustdoc's JSON output for the library and flattens every public item into one line
+//! of `api-surface.txt`, like `pub fn relay_st_jude::Campaign::fetch() -> Result<Self, Report>`.
+//! If the surface changes without the snapshot changing with it, this test fails and lists
+//! what was added and removed. To accept a change, regenerate the snapshot:
+//!
+//! ```sh
+//! UPDATE_API_SNAPSHOT=1 cargo test --test api_surface
+//! ```
+//!
+//! Rustdoc JSON is only available on nightly, through `cargo +nightly`. Without a nightly
+//! toolchain this test says so and passes.
+
+use serde_json::Value;
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SNAPSHOT: &str = "api-surface.txt";
+
+/// Build rustdoc JSON for the library, or `None` if there's no nightly toolchain
+fn rustdoc_json() -> Option<Value> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // A separate target dir, so that we don't wait on the lock held by the `cargo test` running us
+    let target_dir = manifest_dir.join("target").join("api-surface");
+
+    let output = Command::new("cargo")
+        .args([
+            "+nightly",
+            "rustdoc",
+            "--lib",
+            "--all-features",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .current_dir(manifest_dir)
+        .env_remove("RUSTUP_TOOLCHAIN")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("toolchain") && stderr.contains("not installed") {
+            return None;
+        }
+        panic!("cargo +nightly rustdoc failed:\n{}", stderr);
+    }
+
+    let path: PathBuf = target_dir.join("doc").join("relay_st_jude.json");
+    let json = std::fs::read_to_string(&path).unwrap();
+    Some(serde_json::from_str(&json).unwrap())
+}
+
+/// Flattens rustdoc JSON into one line per public item
+struct Surface<'a> {
+    index: &'a serde_json::Map<String, Value>,
+    lines: BTreeSet<String>,
+    seen: HashSet<String>,
+}
+
+impl<'a> Surface<'a> {
+    fn new(doc: &'a Value) -> Self {
+        let mut surface = Surface {
+            index: doc["index"].as_object().unwrap(),
+            lines: BTreeSet::new(),
+            seen: HashSet::new(),
+        };
+
+        let root = surface.item(&doc["root"]).unwrap();
+        let mut uses = vec![];
+        surface.module(root, "relay_st_jude", &mut uses);
+
+        // Re-exports of items that aren't reachable any other way are documented as if they
+        // lived where they're re-exported, at the shortest such path. Anything else is just a
+        // `pub use`.
+        uses.sort_by_key(|(path, _)| (path.matches("::").count(), path.clone()));
+        for (path, item) in uses {
+            let target = &item["inner"]["use"];
+            match surface.item(&target["id"]) {
+                Some(inlined) if !surface.seen.contains(&id(&target["id"])) => {
+                    surface.seen.insert(id(&target["id"]));
+                    surface.item_lines(inlined, &path);
+                }
+                _ => {
+                    surface.lines.insert(format!(
+                        "pub use {} = {}",
+                        path,
+                        target["source"].as_str().unwrap()
+                    ));
+                }
+            }
+        }
+
+        surface
+    }
+
+    fn item(&self, id: &Value) -> Option<&'a Value> {
+        self.index.get(&self::id(id))
+    }
+
+    fn module(&mut self, module: &'a Value, path: &str, uses: &mut Vec<(String, &'a Value)>) {
+        self.lines.insert(format!("pub mod {}", path));
+
+        for child in module["inner"]["module"]["items"].as_array().unwrap() {
+            let child = match self.item(child) {
+                Some(child) if child["visibility"] == "public" => child,
+                _ => continue,
+            };
+
+            if child["inner"].get("use").is_some() {
+                let name = child["inner"]["use"]["name"].as_str().unwrap();
+                uses.push((format!("{}::{}", path, name), child));
+                continue;
+            }
+
+            self.seen.insert(id(&child["id"]));
+            let child_path = format!("{}::{}", path, child["name"].as_str().unwrap());
+            if child["inner"].get("module").is_some() {
+                self.module(child, &child_path, uses);
+            } else {
+                self.item_lines(child, &child_path);
+            }
+        }
+    }
+
+    fn item_lines(&mut self, item: &'a Value, path: &str) {
+        let (kind, inner) = inner(item);
+        match kind {
+            "struct" => {
+                self.lines.insert(format!(
+                    "pub struct {}{}",
+                    path,
+                    self.generics(&inner["generics"])
+                ));
+                match &inner["kind"] {
+                    Value::Object(kind) if kind.contains_key("plain") => {
+                        self.fields(&kind["plain"]["fields"], path)
+                    }
+                    Value::Object(kind) if kind.contains_key("tuple") => {
+                        self.fields(&kind["tuple"], path)
+                    }
+                    _ => {}
+                }
+                self.impls(&inner["impls"], path);
+            }
+            "enum" => {
+                self.lines.insert(format!(
+                    "pub enum {}{}",
+                    path,
+                    self.generics(&inner["generics"])
+                ));
+                for variant in inner["variants"].as_array().unwrap() {
+                    let variant = self.item(variant).unwrap();
+                    let variant_path = format!("{}::{}", path, variant["name"].as_str().unwrap());
+                    self.lines.insert(format!("pub variant {}", variant_path));
+
+                    if let Value::Object(kind) = &variant["inner"]["variant"]["kind"] {
+                        if let Some(fields) = kind.get("tuple") {
+                            self.fields(fields, &variant_path);
+                        } else if let Some(fields) = kind.get("struct") {
+                            self.fields(&fields["fields"], &variant_path);
+                        }
+                    }
+                }
+                self.impls(&inner["impls"], path);
+            }
+            "function" => {
+                let line = self.function(inner, path);
+                self.lines.insert(line);
+            }
+            "constant" => {
+                self.lines
+                    .insert(format!("pub const {}: {}", path, self.ty(&inner["type"])));
+            }
+            "static" => {
+                self.lines
+                    .insert(format!("pub static {}: {}", path, self.ty(&inner["type"])));
+            }
+            _ => {
+                self.lines.insert(format!("pub {} {}", kind, path));
+            }
+        }
+    }
+
+    fn fields(&mut self, fields: &Value, path: &str) {
+        for (i, field) in fields.as_array().unwrap().iter().enumerate() {
+            let field = match self.item(field) {
+                Some(field) => field,
+                // Private tuple fields show up as nulls
+                None => continue,
+            };
+            let name = match field["name"].as_str() {
+                // Tuple fields are named "0", "1", ...
+                Some(name) => name.to_string(),
+                None => i.to_string(),
+            };
+            self.lines.insert(format!(
+                "pub field {}::{}: {}",
+                path,
+                name,
+                self.ty(&field["inner"]["struct_field"])
+            ));
+        }
+    }
+
+    fn impls(&mut self, impls: &Value, path: &str) {
+        for id in impls.as_array().unwrap() {
+            let imp = &self.item(id).unwrap()["inner"]["impl"];
+
+            // Auto traits, blanket impls, and compiler-internal markers come and go with the
+            // compiler, not with us
+            if imp["is_synthetic"] == true
+                || !imp["blanket_impl"].is_null()
+                || imp["trait"]["path"] == "StructuralPartialEq"
+            {
+                continue;
+            }
+
+            if imp["trait"].is_null() {
+                for item in imp["items"].as_array().unwrap() {
+                    let item = self.item(item).unwrap();
+                    if item["visibility"] != "public" {
+                        continue;
+                    }
+                    let item_path = format!("{}::{}", path, item["name"].as_str().unwrap());
+                    self.item_lines(item, &item_path);
+                }
+            } else {
+                self.lines.insert(format!(
+                    "impl{} {} for {}",
+                    self.generics(&imp["generics"]),
+                    self.path(&imp["trait"]),
+                    path
+                ));
+            }
+        }
+    }
+
+    fn function(&self, function: &Value, path: &str) -> String {
+        let header = &function["header"];
+        let mut line = String::from("pub ");
+        if header["is_const"] == true {
+            line.push_str("const ");
+        }
+        if header["is_async"] == true {
+            line.push_str("async ");
+        }
+        if header["is_unsafe"] == true {
+            line.push_str("unsafe ");
+        }
+        if let Value::Object(abi) = &header["abi"] {
+            let abi = abi.keys().next().unwrap();
+            line.push_str(&format!("extern {:?} ", abi));
+        }
+
+        let sig = &function["sig"];
+        let inputs: Vec<_> = sig["inputs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|input| {
+                let name = input[0].as_str().unwrap();
+                let ty = self.ty(&input[1]);
+                match (name, ty.as_str()) {
+                    ("self", "Self") => "self".to_string(),
+                    ("self", "&Self") => "&self".to_string(),
+                    ("self", "&mut Self") => "&mut self".to_string(),
+                    _ => format!("{}: {}", name, ty),
+                }
+            })
+            .collect();
+
+        line.push_str(&format!(
+            "fn {}{}({})",
+            path,
+            self.generics(&function["generics"]),
+            inputs.join(", ")
+        ));
+        if !sig["output"].is_null() {
+            line.push_str(&format!(" -> {}", self.ty(&sig["output"])));
+        }
+
+        line
+    }
+
+    fn generics(&self, generics: &Value) -> String {
+        let params: Vec<_> = generics["params"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|param| {
+                let name = param["name"].as_str().unwrap();
+                let (kind, inner) = inner(param);
+                match kind {
+                    // `impl Trait` arguments are listed as generics too, but show up in the
+                    // argument list already
+                    "type" if inner["is_synthetic"] == true => None,
+                    "type" => Some(self.bounded(name, &inner["bounds"])),
+                    "lifetime" => Some(name.to_string()),
+                    "const" => Some(format!("const {}: {}", name, self.ty(&inner["type"]))),
+                    _ => Some(name.to_string()),
+                }
+            })
+            .collect();
+
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", params.join(", "))
+        }
+    }
+
+    fn bounded(&self, name: &str, bounds: &Value) -> String {
+        let bounds = self.bounds(bounds);
+        if bounds.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}: {}", name, bounds)
+        }
+    }
+
+    fn bounds(&self, bounds: &Value) -> String {
+        bounds
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|bound| match inner(bound) {
+                ("trait_bound", inner) => self.path(&inner["trait"]),
+                ("outlives", lifetime) => lifetime.as_str().unwrap().to_string(),
+                (kind, _) => kind.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    /// A path to a type or trait, like `Result<Self, Report>`
+    ///
+    /// Only the last segment of the path is kept, so that `crate::Usd` and `Usd` are the same
+    /// thing, and moving a `use` around doesn't change the surface.
+    fn path(&self, path: &Value) -> String {
+        let full = path["path"].as_str().unwrap();
+        let mut out = full.rsplit("::").next().unwrap().to_string();
+
+        match &path["args"] {
+            Value::Object(args) if args.contains_key("angle_bracketed") => {
+                let args = &args["angle_bracketed"];
+                let mut parts: Vec<_> = args["args"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|arg| match inner(arg) {
+                        ("type", ty) => self.ty(ty),
+                        (_, other) => other
+                            .as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| other.to_string()),
+                    })
+                    .collect();
+                for constraint in args["constraints"].as_array().unwrap() {
+                    let binding = match inner(&constraint["binding"]) {
+                        ("equality", term) => format!("= {}", self.ty(&term["type"])),
+                        ("constraint", bounds) => format!(": {}", self.bounds(bounds)),
+                        (kind, _) => kind.to_string(),
+                    };
+                    parts.push(format!(
+                        "{} {}",
+                        constraint["name"].as_str().unwrap(),
+                        binding
+                    ));
+                }
+                if !parts.is_empty() {
+                    out.push_str(&format!("<{}>", parts.join(", ")));
+                }
+            }
+            Value::Object(args) if args.contains_key("parenthesized") => {
+                let args = &args["parenthesized"];
+                let inputs: Vec<_> = args["inputs"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|ty| self.ty(ty))
+                    .collect();
+                out.push_str(&format!("({})", inputs.join(", ")));
+                if !args["output"].is_null() {
+                    out.push_str(&format!(" -> {}", self.ty(&args["output"])));
+                }
+            }
+            _ => {}
+        }
+
+        out
+    }
+
+    fn ty(&self, ty: &Value) -> String {
+        match inner(ty) {
+            ("primitive", name) | ("generic", name) => name.as_str().unwrap().to_string(),
+            ("resolved_path", path) => self.path(path),
+            ("borrowed_ref", r) => format!(
+                "&{}{}{}",
+                r["lifetime"]
+                    .as_str()
+                    .map(|l| format!("{} ", l))
+                    .unwrap_or_default(),
+                if r["is_mutable"] == true { "mut " } else { "" },
+                self.ty(&r["type"])
+            ),
+            ("raw_pointer", p) => format!(
+                "*{} {}",
+                if p["is_mutable"] == true {
+                    "mut"
+                } else {
+                    "const"
+                },
+                self.ty(&p["type"])
+            ),
+            ("slice", ty) => format!("[{}]", self.ty(ty)),
+            ("array", array) => format!(
+                "[{}; {}]",
+                self.ty(&array["type"]),
+                array["len"].as_str().unwrap()
+            ),
+            ("tuple", types) => format!(
+                "({})",
+                types
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|ty| self.ty(ty))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ("impl_trait", bounds) => format!("impl {}", self.bounds(bounds)),
+            ("dyn_trait", dyn_trait) => format!(
+                "dyn {}",
+                dyn_trait["traits"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|t| self.path(&t["trait"]))
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            ),
+            ("qualified_path", q) => format!(
+                "<{} as {}>::{}",
+                self.ty(&q["self_type"]),
+                self.path(&q["trait"]),
+                q["name"].as_str().unwrap()
+            ),
+            ("infer", _) => "_".to_string(),
+            (kind, _) => format!("<{}>", kind),
+        }
+    }
+}
+
+fn id(id: &Value) -> String {
+    id.to_string()
+}
+
+/// rustdoc JSON enums are objects with a single key naming the variant
+fn inner(value: &Value) -> (&str, &Value) {
+    let object = match value.get("inner") {
+        Some(inner) => inner,
+        None => value.get("kind").unwrap_or(value),
+    };
+    match object {
+        Value::Object(map) if map.len() == 1 => {
+            let (kind, inner) = map.iter().next().unwrap();
+            (kind.as_str(), inner)
+        }
+        Value::String(kind) => (kind.as_str(), object),
+        _ => panic!("unexpected rustdoc json: {}", object),
+    }
+}
+
+#[test]
+fn public_api_matches_snapshot() {
+    let doc = match rustdoc_json() {
+        Some(doc) => doc,
+        None => {
+            eprintln!("Skipping the public API check: no nightly toolchain for rustdoc JSON");
+            return;
+        }
+    };
+
+    let surface = Surface::new(&doc).lines;
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT);
+
+    if std::env::var_os("UPDATE_API_SNAPSHOT").is_some() {
+        let mut snapshot: String = surface.iter().map(|line| format!("{}\n", line)).collect();
+        snapshot.insert_str(
+            0,
+            "# The public API of relay-st-jude, generated by tests/api_surface.rs\n\
+             # Regenerate with UPDATE_API_SNAPSHOT=1 cargo test --test api_surface\n",
+        );
+        std::fs::write(&path, snapshot).unwrap();
+        return;
+    }
+
+    let snapshot = std::fs::read_to_string(&path).unwrap_or_default();
+    let expected: BTreeSet<String> = snapshot
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let removed: Vec<_> = expected.difference(&surface).collect();
+    let added: Vec<_> = surface.difference(&expected).collect();
+
+    if !removed.is_empty() || !added.is_empty() {
+        let mut report = String::from("The public API changed:\n");
+        for line in removed {
+            report.push_str(&format!("  - {}\n", line));
+        }
+        for line in added {
+            report.push_str(&format!("  + {}\n", line));
+        }
+        report.push_str(&format!(
+            "\nIf this is intended, regenerate {} with UPDATE_API_SNAPSHOT=1",
+            SNAPSHOT
+        ));
+        panic!("{}", report);
+    }
+}
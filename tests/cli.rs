@@ -0,0 +1,296 @@
+//! Run the CLI binary against a local mock of the Tiltify API
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use std::thread;
+
+const RESPONSE: &str = include_str!("../src/example-response.json");
+
+/// Serve `body` to exactly one HTTP request, returning the URL to send it to
+fn serve_once(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        // Read the headers, then exactly as much body as we were promised
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = len.trim().parse().unwrap();
+            }
+        }
+        let mut request_body = vec![0; content_length];
+        reader.read_exact(&mut request_body).unwrap();
+
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+    });
+
+    url
+}
+
+fn run(args: &[&str]) -> (String, String) {
+    run_with(RESPONSE, args)
+}
+
+/// Like [`run`], but the mock API responds with `response`
+fn run_with(response: &'static str, args: &[&str]) -> (String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_relay-st-jude"))
+        .args(args)
+        .env("TILTIFY_API_URL", serve_once(response))
+        .env_remove("RUST_LOG")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    (
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn very_verbose_logs_to_stderr_only() {
+    let (stdout, stderr) = run(&["-vv"]);
+
+    assert!(stderr.contains("DEBUG"), "{}", stderr);
+    assert!(stderr.contains("sending GraphQL request"), "{}", stderr);
+    assert!(stderr.contains("received GraphQL response"), "{}", stderr);
+    assert!(stderr.contains("fetched campaign"), "{}", stderr);
+
+    assert!(
+        stdout.starts_with("Relay FM for St. Jude 2021!"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("DEBUG"), "{}", stdout);
+    assert!(!stdout.contains("INFO"), "{}", stdout);
+}
+
+#[test]
+fn default_is_quiet_on_success() {
+    let (stdout, stderr) = run(&[]);
+
+    assert_eq!(stderr, "");
+    assert!(
+        stdout.starts_with("Relay FM for St. Jude 2021!"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn suggest() {
+    let (stdout, _) = run(&["suggest", "--count", "3"]);
+
+    assert_eq!(
+        stdout,
+        concat!(
+            "Relay FM for St. Jude 2021!\n",
+            "$22,663.40 of $333,333.33\n",
+            "Suggested milestones:\n",
+            "        $150,000.00 - between $75,000.00 and $196,060.44\n",
+            "        $250,000.00 - between $196,060.44 and $333,333.33\n",
+            "        $300,000.00 - between $250,000.00 and $333,333.33\n",
+        )
+    );
+}
+
+#[test]
+fn unpriced_milestones_sort_last() {
+    let (stdout, stderr) = run_with(
+        include_str!("../src/example-response-null-amount.json"),
+        &[],
+    );
+
+    assert_eq!(
+        stdout,
+        concat!(
+            "Relay FM for St. Jude 2021!\n",
+            "$22,663.40 of $333,333.33 (6.8%)\n",
+            "    ✅            $20,000.00 - Myke and Stephen attempt Flight Simulator again\n",
+            "    🤞 41.2%      $55,000.00 - Stephen dissembles his NeXTCube on stream\n",
+            "                           — - Something secret\n",
+        )
+    );
+    assert!(stderr.contains("milestone has no amount yet"), "{}", stderr);
+}
+
+#[test]
+fn limited_listing() {
+    let (stdout, _) = run(&["--sort", "remaining", "--limit", "2", "--page", "1"]);
+
+    assert_eq!(
+        stdout,
+        concat!(
+            "Relay FM for St. Jude 2021!\n",
+            "$22,663.40 of $333,333.33 (6.8%)\n",
+            "    🤞 41.2%      $55,000.00 - Stephen dissembles his NeXTCube on stream\n",
+            "    🤞 30.2%      $75,000.00 - Stephen & Myke go to space via KSP\n",
+            "    … and 2 more (use --limit 0 for all)\n",
+        )
+    );
+}
+
+#[test]
+fn project() {
+    let (stdout, _) = run(&["project", "--over", "48h", "--rate", "1000"]);
+
+    assert_eq!(
+        stdout,
+        concat!(
+            "Relay FM for St. Jude 2021!\n",
+            "$22,663.40 of $333,333.33, at $1,000.00 an hour\n",
+            "Projected over the next 48h:\n",
+            "         $55,000.00 - in 32h 20m (26h 56m to 40h 25m) - Stephen dissembles his NeXTCube on stream\n",
+            "         $75,000.00 - maybe, in 43h 36m at the soonest - Stephen & Myke go to space via KSP\n",
+            "        $196,060.44 - not in time - $1 million raised in 3 years!\n",
+            "        $333,333.33 - not in time - Goal\n",
+        )
+    );
+}
+
+/// `--hours` still works, but says what to use instead
+#[test]
+fn project_hours_is_deprecated() {
+    let (stdout, stderr) = run(&["project", "--hours", "36", "--rate", "1000"]);
+
+    assert!(
+        stdout.contains("Projected over the next 36h:"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stderr.contains("--hours is deprecated, use --over 36h"),
+        "{}",
+        stderr
+    );
+}
+
+/// Run with bad arguments, which fail before fetching anything, returning stderr
+fn run_invalid(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_relay-st-jude"))
+        .args(args)
+        // Nothing is listening here, so getting as far as fetching fails differently
+        .env("TILTIFY_API_URL", "http://127.0.0.1:9")
+        .output()
+        .unwrap();
+    assert!(!output.status.success(), "{:?}", output);
+
+    String::from_utf8(output.stderr).unwrap()
+}
+
+/// Too many hours to count in seconds is a bad value, not an overflow
+#[test]
+fn project_hours_overflow() {
+    let stderr = run_invalid(&["project", "--hours", &u64::MAX.to_string()]);
+
+    assert!(
+        stderr.contains(&format!("Invalid --hours \"{}\": too many hours", u64::MAX)),
+        "{}",
+        stderr
+    );
+}
+
+/// Projecting centuries ahead would take forever, so it's refused up front
+#[test]
+fn project_horizon_is_capped() {
+    let too_far = ["project", "--over", "1000000000h", "--rate", "1000"];
+    let stderr = run_invalid(&[&too_far[..], &["--boost", "18:00-19:00=2"]].concat());
+    assert!(
+        stderr.contains("Can't project more than 8760h ahead, got 1000000000h"),
+        "{}",
+        stderr
+    );
+
+    let stderr = run_invalid(&["project", "--hours", "8761", "--rate", "1000"]);
+    assert!(
+        stderr.contains("Can't project more than 8760h ahead"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn selftest_runs_offline() {
+    let output = Command::new(env!("CARGO_BIN_EXE_relay-st-jude"))
+        .arg("--selftest")
+        // Nothing is listening here, so any network access would fail the run
+        .env("TILTIFY_API_URL", "http://127.0.0.1:9")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(
+        stdout.starts_with(&format!(
+            "relay-st-jude {} self-test\n",
+            env!("CARGO_PKG_VERSION")
+        )),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("ok   parse "), "{}", stdout);
+    assert!(stdout.ends_with(" checks passed\n"), "{}", stdout);
+}
+
+/// The fact sheet asks for less, and copes with the smaller response
+#[test]
+fn fact_sheet_skips_the_description() {
+    let (stdout, stderr) = run_with(
+        include_str!("../src/example-response-summary.json"),
+        &["--format", "fact-sheet", "-vv"],
+    );
+    let sheet: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(sheet["raised_usd"], 22663.4);
+    assert_eq!(
+        sheet["next_milestone"]["description"],
+        "Stephen dissembles his NeXTCube on stream"
+    );
+    assert!(
+        stderr.contains("get_campaign_summary_by_vanity_and_slug"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn canonical_json() {
+    let (stdout, _) = run(&["--json-canonical"]);
+    let (again, _) = run(&["--json-canonical"]);
+
+    assert_eq!(stdout, again);
+    assert!(stdout.ends_with("}\n"), "{}", stdout);
+    assert!(stdout.contains(r#""value": "22663.40""#), "{}", stdout);
+}
+
+#[test]
+fn fact_sheet_is_json() {
+    let (stdout, _) = run(&["--format", "fact-sheet"]);
+    let sheet: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(sheet["v"], 1);
+    assert_eq!(sheet["name"], "Relay FM for St. Jude 2021");
+    assert_eq!(
+        sheet["campaign_url"],
+        "https://tiltify.com/@relay-fm/relay-st-jude-21"
+    );
+    assert!(sheet["fetched_at"].is_string(), "{}", stdout);
+}
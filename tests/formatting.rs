@@ -0,0 +1,247 @@
+//! Golden-file tests for how money is displayed
+//!
+//! Every combination of amount × rounding policy × style is rendered into one line of
+//! `tests/formatting.golden`. Styles cover widths, fills, precisions, the alternate flag,
+//! locales, and the compact form. Any change to formatting, intended or not, shows up as a diff of
+//! that file. To accept a change, regenerate it:
+//!
+//! ```sh
+//! UPDATE_GOLDEN=1 cargo test --test formatting
+//! ```
+
+use relay_st_jude::{Locale, RoundingPolicy, Usd};
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const GOLDEN: &str = "tests/formatting.golden";
+
+/// At most this many differences are printed when the golden file is out of date
+const MAX_REPORTED: usize = 20;
+
+const AMOUNTS: &[f64] = &[
+    0.,
+    0.001,
+    0.004,
+    0.005,
+    0.006,
+    0.015,
+    0.025,
+    0.035,
+    0.099,
+    0.1,
+    0.994,
+    0.995,
+    0.999,
+    1.,
+    1.005,
+    1.015,
+    2.675,
+    9.995,
+    10.,
+    19.99,
+    99.995,
+    100.,
+    999.994,
+    999.995,
+    999.999,
+    1_000.,
+    1_234.565,
+    5_000.5,
+    9_999.995,
+    20_000.,
+    22_663.40,
+    55_000.,
+    99_999.999,
+    100_000.005,
+    196_060.44,
+    333_333.33,
+    999_999.995,
+    1_000_000.,
+    12_345_678.905,
+    1e9,
+    -0.004,
+    -0.005,
+    -1.,
+];
+
+const POLICIES: &[RoundingPolicy] = &[
+    RoundingPolicy::HalfUp,
+    RoundingPolicy::HalfEven,
+    RoundingPolicy::Floor,
+    RoundingPolicy::Ceiling,
+];
+
+type Style = (&'static str, fn(Usd) -> String);
+
+/// Format strings can't be built at runtime, so each format spec is its own closure
+const STYLES: &[Style] = &[
+    ("plain", |usd| format!("{}", usd)),
+    ("width-4", |usd| format!("{:4}", usd)),
+    ("width-15", |usd| format!("{:15}", usd)),
+    ("left-15", |usd| format!("{:<15}", usd)),
+    ("fill-*^15", |usd| format!("{:*^15}", usd)),
+    ("prec-0", |usd| format!("{:.0}", usd)),
+    ("prec-1", |usd| format!("{:.1}", usd)),
+    ("prec-3", |usd| format!("{:.3}", usd)),
+    ("prec-0-w10", |usd| format!("{:>10.0}", usd)),
+    ("alt", |usd| format!("{:#}", usd)),
+    ("alt-w15", |usd| format!("{:#15}", usd)),
+    ("de", |usd| usd.display_with_locale(Locale::de).to_string()),
+    ("fr", |usd| usd.display_with_locale(Locale::fr).to_string()),
+    ("en_IN", |usd| {
+        usd.display_with_locale(Locale::en_IN).to_string()
+    }),
+    ("de-alt-.1", |usd| {
+        format!("{:#.1}", usd.display_with_locale(Locale::de))
+    }),
+    ("compact", |usd| usd.compact()),
+];
+
+/// One line per case, keyed so that diffs can say which case changed
+///
+/// Cases stay in enumeration order, so that related lines sit together in the golden file.
+fn render_cases() -> Vec<(String, String)> {
+    let mut cases = vec![];
+
+    for &amount in AMOUNTS {
+        for &policy in POLICIES {
+            for &(style, render) in STYLES {
+                let key = format!(
+                    "{:>18} {:<10} {:<10}",
+                    format!("{:?}", amount),
+                    format!("{:?}", policy),
+                    style
+                );
+                let rendered = render(Usd::new_rounded(amount, policy));
+
+                cases.push((key, format!("|{}|", rendered)));
+            }
+        }
+    }
+
+    cases
+}
+
+fn to_golden(cases: &[(String, String)]) -> String {
+    let mut golden = String::from(
+        "# Generated by tests/formatting.rs. Regenerate with UPDATE_GOLDEN=1.\n\
+         #           amount policy     style      | output |\n",
+    );
+
+    for (key, rendered) in cases {
+        golden.push_str(key);
+        golden.push(' ');
+        golden.push_str(rendered);
+        golden.push('\n');
+    }
+
+    golden
+}
+
+fn parse_golden(golden: &str) -> Vec<(String, String)> {
+    golden
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let split = line.find(" |")?;
+            Some((line[..split].to_string(), line[split + 1..].to_string()))
+        })
+        .collect()
+}
+
+/// Describe how `actual` differs from `expected`, or `None` if they match
+fn diff(expected: &[(String, String)], actual: &[(String, String)]) -> Option<String> {
+    let expected_by_key: BTreeMap<_, _> = expected.iter().cloned().collect();
+    let actual_by_key: BTreeMap<_, _> = actual.iter().cloned().collect();
+    let mut lines = vec![];
+
+    for (key, want) in expected {
+        match actual_by_key.get(key) {
+            Some(got) if got == want => {}
+            Some(got) => lines.push(format!("changed {}\n    - {}\n    + {}", key, want, got)),
+            None => lines.push(format!("removed {}\n    - {}", key, want)),
+        }
+    }
+    for (key, got) in actual {
+        if !expected_by_key.contains_key(key) {
+            lines.push(format!("added   {}\n    + {}", key, got));
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    let total = lines.len();
+    lines.truncate(MAX_REPORTED);
+    if total > MAX_REPORTED {
+        lines.push(format!("... and {} more", total - MAX_REPORTED));
+    }
+
+    Some(format!(
+        "{} of {} formatting cases differ from {}:\n{}\n\n\
+         If this is intended, regenerate with UPDATE_GOLDEN=1 and review the diff.",
+        total,
+        actual.len().max(expected.len()),
+        GOLDEN,
+        lines.join("\n")
+    ))
+}
+
+#[test]
+fn matches_golden() {
+    let cases = render_cases();
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDEN);
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, to_golden(&cases)).unwrap();
+        return;
+    }
+
+    let golden = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Couldn't read {}, try UPDATE_GOLDEN=1: {}", GOLDEN, e));
+
+    if let Some(report) = diff(&parse_golden(&golden), &cases) {
+        panic!("{}", report);
+    }
+}
+
+#[test]
+fn enough_cases() {
+    assert!(render_cases().len() >= 500);
+}
+
+#[test]
+fn golden_round_trips() {
+    let cases = render_cases();
+
+    assert_eq!(parse_golden(&to_golden(&cases)), cases);
+}
+
+#[test]
+fn diff_reports_each_kind_of_change() {
+    let expected: Vec<_> = vec![("a", "|1|"), ("b", "|2|")]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let actual: Vec<_> = vec![("a", "|1|"), ("b", "|3|"), ("c", "|4|")]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    let report = diff(&expected, &actual).unwrap();
+    assert!(
+        report.starts_with("2 of 3 formatting cases differ"),
+        "{}",
+        report
+    );
+    assert!(
+        report.contains("changed b\n    - |2|\n    + |3|"),
+        "{}",
+        report
+    );
+    assert!(report.contains("added   c\n    + |4|"), "{}", report);
+
+    assert_eq!(diff(&expected, &expected), None);
+}
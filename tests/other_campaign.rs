@@ -0,0 +1,106 @@
+//! Everything but the `relay` module works for any campaign, not just Relay's
+//!
+//! These run the library's features over a made-up campaign that has nothing to do with Relay.
+
+use relay_st_jude::completion::{CompletionRules, Context};
+use relay_st_jude::duration::HumanDuration;
+use relay_st_jude::overlay::to_overlay_frame;
+use relay_st_jude::simulate::{self, Assumptions, Sample};
+use relay_st_jude::template::Template;
+use relay_st_jude::{Campaign, CampaignId, ListOptions, Usd};
+
+use std::time::{Duration, UNIX_EPOCH};
+
+const RESPONSE: &str = include_str!("other-campaign.json");
+
+fn campaign() -> Campaign {
+    Campaign::from_slice(RESPONSE.as_bytes()).unwrap()
+}
+
+#[test]
+fn parses() {
+    let campaign = campaign();
+
+    assert_eq!(campaign.id, Some(CampaignId::new("98765")));
+    assert_eq!(campaign.name, "Bake Sale Marathon");
+    assert_eq!(campaign.total_amount_raised, Usd::new(1_250.));
+}
+
+#[test]
+fn lists_and_completes() {
+    let campaign = campaign();
+    let page = campaign.milestone_page(&ListOptions::default());
+    let names: Vec<_> = page
+        .milestones
+        .iter()
+        .map(|m| m.description.as_str())
+        .collect();
+    assert_eq!(names, ["Bread in a slow cooker", "Croissants from scratch"]);
+
+    let mut before = campaign.clone();
+    before.total_amount_raised = Usd::new(900.);
+    let newly =
+        CompletionRules::new().newly_completed(&Context::new(&before), &Context::new(&campaign));
+    assert_eq!(newly.len(), 1);
+    assert_eq!(newly[0].description, "Bread in a slow cooker");
+}
+
+#[test]
+fn renders() {
+    let campaign = campaign();
+
+    let sheet = campaign
+        .fact_sheet()
+        .source("https://tiltify.com/@bakers/bake-sale", UNIX_EPOCH);
+    assert_eq!(sheet.name, "Bake Sale Marathon");
+    assert_eq!(sheet.milestones_completed, 1);
+    assert!(!sheet.to_json().contains("relay"), "{}", sheet.to_json());
+
+    let template =
+        Template::parse("{campaign.name}: {campaign.raised} of {campaign.goal}").unwrap();
+    assert_eq!(
+        template.render(&campaign, None),
+        "Bake Sale Marathon: $1,250.00 of $5,000.00"
+    );
+
+    let frame = to_overlay_frame(None, &campaign, 1);
+    assert_eq!(frame.total_usd, 1_250.);
+}
+
+#[test]
+fn exports() {
+    let campaign = campaign();
+    let json = campaign.to_canonical_json();
+
+    assert!(json.contains(r#""value": "1250.00""#), "{}", json);
+    assert_eq!(
+        Campaign::from_slice(json.as_bytes())
+            .unwrap()
+            .to_canonical_json(),
+        json
+    );
+}
+
+#[test]
+fn plans() {
+    let campaign = campaign();
+    assert!(!campaign.suggest_milestones(2).is_empty());
+
+    let horizon: HumanDuration = "24h".parse().unwrap();
+    let assumptions = Assumptions {
+        rate_per_hour: Some(Usd::new(100.)),
+        ..Assumptions::new(horizon.into())
+    };
+    let history = [Sample {
+        at: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        total: campaign.total_amount_raised,
+    }];
+    let projection = simulate::project(&campaign, &history, &assumptions).unwrap();
+
+    let croissants = projection
+        .crossings
+        .iter()
+        .find(|c| c.description == "Croissants from scratch")
+        .unwrap();
+    assert_eq!(croissants.after, Some(Duration::from_secs(45_000)));
+}
@@ -0,0 +1,48 @@
+//! The prelude is public API: this pins down exactly what it exports, and that a glob import of
+//! it doesn't clash with the standard prelude.
+
+// Nothing but the prelude on purpose
+use relay_st_jude::prelude::*;
+
+#[test]
+fn prelude_is_enough() {
+    let campaign: Campaign = Campaign {
+        id: Some(CampaignId::new("1")),
+        name: "Test".to_string(),
+        slug: None,
+        description: String::new(),
+        status: Some(CampaignStatus::Published),
+        avatar: None,
+        team: None,
+        livestream: None,
+        cause: None,
+        total_amount_raised: Usd::new(250.),
+        goal: Usd::new(1_000.),
+        original_goal: None,
+        published_at: None,
+        ends_at: None,
+        milestones: vec![Milestone {
+            id: None,
+            description: "A start".to_string(),
+            amount: Some(Usd::new(100.)),
+        }],
+        rewards: vec![],
+        page: None,
+    };
+
+    let gaps: Vec<MilestoneGap> = campaign.milestone_gaps();
+    let suggestions: Vec<MilestoneSuggestion> = campaign.suggest_milestones(1);
+    let frame: OverlayFrame = relay_st_jude::overlay::to_overlay_frame(None, &campaign, 0);
+    let rounded: Usd = Usd::new_rounded(campaign.goal.usd(), RoundingPolicy::HalfUp);
+    let report: Report = Report::msg("just checking");
+
+    assert_eq!(gaps.len(), 2);
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(frame.total_usd, 250.);
+    assert_eq!(rounded, Usd::new(1_000.));
+    assert_eq!(report.to_string(), "just checking");
+
+    // The standard prelude still means what it usually does
+    let ok: Result<(), String> = Ok(());
+    assert!(ok.is_ok());
+}